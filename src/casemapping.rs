@@ -0,0 +1,51 @@
+use serde_derive::{Serialize, Deserialize};
+
+/// The casemapping used to compare nicknames, channel names and masks.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Debug, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum Casemapping {
+
+    /// Only `A-Z` are the uppercase forms of `a-z`
+    Ascii,
+
+    /// `A-Z` and `[]\^` are the uppercase forms of `a-z` and `{}|~`
+    #[default]
+    Rfc1459,
+
+    /// `A-Z` and `[]\` are the uppercase forms of `a-z` and `{}|`
+    Rfc1459Strict,
+}
+
+impl Casemapping {
+
+    /// Gets the name of the casemapping, as advertised through ISUPPORT.
+    pub fn name(self) -> &'static str {
+        match self {
+            Casemapping::Ascii => "ascii",
+            Casemapping::Rfc1459 => "rfc1459",
+            Casemapping::Rfc1459Strict => "rfc1459-strict",
+        }
+    }
+
+    /// Folds a single character to its lowercase form.
+    pub fn fold_char(self, chr: char) -> char {
+        match (self, chr) {
+            (_, 'A'..='Z') => chr.to_ascii_lowercase(),
+            (Casemapping::Rfc1459, '[') | (Casemapping::Rfc1459Strict, '[') => '{',
+            (Casemapping::Rfc1459, ']') | (Casemapping::Rfc1459Strict, ']') => '}',
+            (Casemapping::Rfc1459, '\\') | (Casemapping::Rfc1459Strict, '\\') => '|',
+            (Casemapping::Rfc1459, '^') => '~',
+            _ => chr,
+        }
+    }
+
+    /// Folds a string to its lowercase form.
+    pub fn fold(self, text: &str) -> String {
+        text.chars().map(|chr| self.fold_char(chr)).collect()
+    }
+
+    /// Tests whether two strings are equal under this casemapping.
+    pub fn equals(self, a: &str, b: &str) -> bool {
+        a.chars().count() == b.chars().count() && a.chars().zip(b.chars()).all(|(a, b)| self.fold_char(a) == self.fold_char(b))
+    }
+}
\ No newline at end of file
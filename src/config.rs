@@ -1,9 +1,16 @@
 use serde_derive::{Serialize, Deserialize};
 
+use crate::casemapping::Casemapping;
+
 #[derive(Serialize, Deserialize)]
 pub struct ServerConfig {
     server: ServerConfigServer,
     client: Option<ServerConfigClient>,
+    channel: Option<ServerConfigChannel>,
+    connection_class: Option<Vec<ServerConfigConnectionClass>>,
+    oper_class: Option<Vec<ServerConfigOperClass>>,
+    oper: Option<Vec<ServerConfigOper>>,
+    admin: Option<ServerConfigAdmin>,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -11,11 +18,117 @@ pub struct ServerConfigServer {
     listen: String,
     host: String,
     port: u16,
+    network: Option<String>,
+    casemapping: Option<Casemapping>,
+    password: Option<String>,
+    motd_file: Option<String>,
 }
 
 #[derive(Serialize, Deserialize)]
 pub struct ServerConfigClient {
     autojoin: Option<Vec<String>>,
+    nick_length: Option<usize>,
+    registration_timeout: Option<u64>,
+    nick_change_delay: Option<u64>,
+    ping_interval: Option<u64>,
+    ping_timeout: Option<u64>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct ServerConfigChannel {
+    status_modes: Option<String>,
+    max_list_entries: Option<usize>,
+    max_modes: Option<usize>,
+    channel_length: Option<usize>,
+    topic_length: Option<usize>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct ServerConfigConnectionClass {
+    name: String,
+    host: String,
+    password: Option<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct ServerConfigOperClass {
+    name: String,
+    privileges: Vec<OperPrivilege>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct ServerConfigOper {
+    name: String,
+    password: String,
+    host: Option<String>,
+    class: String,
+}
+
+/// Administrative information about the server, sent in reply to ADMIN.
+#[derive(Serialize, Deserialize)]
+pub struct ServerConfigAdmin {
+    location: Option<String>,
+    organization: Option<String>,
+    email: Option<String>,
+}
+
+/// A privilege granted to server operators by their operator class.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Debug)]
+#[serde(rename_all = "lowercase")]
+pub enum OperPrivilege {
+    Kill,
+    Kline,
+    Rehash,
+    Die,
+    Override,
+}
+
+impl ServerConfigOper {
+
+    /// Gets the host mask the operator has to connect from, if any.
+    pub fn host(&self) -> Option<&str> {
+        self.host.as_ref().map(|host| host.as_ref())
+    }
+
+    pub fn class(&self) -> &str {
+        self.class.as_ref()
+    }
+
+    /// Verifies a password against the configured hash.
+    pub fn verify_password(&self, password: &str) -> bool {
+        verify_password(&self.password, password)
+    }
+}
+
+impl ServerConfigAdmin {
+
+    /// Gets the location of the server, e.g. the city and country.
+    pub fn location(&self) -> Option<&str> {
+        self.location.as_ref().map(|location| location.as_ref())
+    }
+
+    /// Gets the organization running the server.
+    pub fn organization(&self) -> Option<&str> {
+        self.organization.as_ref().map(|organization| organization.as_ref())
+    }
+
+    /// Gets the email address of the server administrator.
+    pub fn email(&self) -> Option<&str> {
+        self.email.as_ref().map(|email| email.as_ref())
+    }
+}
+
+/// Verifies a password against an argon2 or bcrypt hash.
+/// 
+/// Plaintext passwords are never accepted.
+pub fn verify_password(hash: &str, password: &str) -> bool {
+    if hash.starts_with("$argon2") {
+        argon2::verify_encoded(hash, password.as_bytes()).unwrap_or(false)
+    } else if hash.starts_with("$2") {
+        bcrypt::verify(password, hash).unwrap_or(false)
+    } else {
+        false
+    }
 }
 
 impl ServerConfig {
@@ -28,6 +141,58 @@ impl ServerConfig {
         (self.server.listen.as_ref(), self.server.port)
     }
 
+    /// Gets the name of the IRC network.
+    pub fn get_network(&self) -> &str {
+        self.server.network.as_ref().map_or(DEFAULT_NETWORK, |network| network.as_ref())
+    }
+
+    /// Gets the number of seconds after which unregistered connections are closed.
+    pub fn get_registration_timeout(&self) -> u64 {
+        self.client.as_ref()
+            .and_then(|client| client.registration_timeout)
+            .unwrap_or(DEFAULT_REGISTRATION_TIMEOUT)
+    }
+
+    /// Gets the minimum number of seconds between two nickname changes.
+    pub fn get_nick_change_delay(&self) -> u64 {
+        self.client.as_ref()
+            .and_then(|client| client.nick_change_delay)
+            .unwrap_or(DEFAULT_NICK_CHANGE_DELAY)
+    }
+
+    /// Gets the number of seconds a client may be idle before it is sent a PING.
+    pub fn get_ping_interval(&self) -> u64 {
+        self.client.as_ref()
+            .and_then(|client| client.ping_interval)
+            .unwrap_or(DEFAULT_PING_INTERVAL)
+    }
+
+    /// Gets the number of seconds to wait for a reply to a PING.
+    pub fn get_ping_timeout(&self) -> u64 {
+        self.client.as_ref()
+            .and_then(|client| client.ping_timeout)
+            .unwrap_or(DEFAULT_PING_TIMEOUT)
+    }
+
+    /// Gets the path of the file containing the message of the day.
+    pub fn get_motd_file(&self) -> &str {
+        self.server.motd_file.as_ref()
+            .map(|motd_file| motd_file.as_ref())
+            .unwrap_or(DEFAULT_MOTD_FILE)
+    }
+
+    /// Gets the casemapping used to compare nicknames and channel names.
+    pub fn get_casemapping(&self) -> Casemapping {
+        self.server.casemapping.unwrap_or_default()
+    }
+
+    /// Gets the maximum length of nicknames.
+    pub fn get_nick_length(&self) -> usize {
+        self.client.as_ref()
+            .and_then(|client| client.nick_length)
+            .unwrap_or(DEFAULT_NICK_LENGTH)
+    }
+
     pub fn get_autojoin_channels(&self) -> Option<Vec<String>> {
         if let Some(client) = &self.client {
             if let Some(channels) = &client.autojoin {
@@ -39,13 +204,140 @@ impl ServerConfig {
             None
         }
     }
+
+    /// Gets the channel member status modes along with their prefixes.
+    /// 
+    /// The modes are configured in the ISUPPORT `PREFIX` format, e.g. `(ohv)@%+`.
+    /// Modes other than `q`, `a`, `o`, `h` and `v` are ignored, and the
+    /// result is always ordered from the highest to the lowest rank.
+    pub fn get_status_modes(&self) -> Vec<(char, char)> {
+        let status_modes = self.channel.as_ref()
+            .and_then(|channel| channel.status_modes.clone())
+            .unwrap_or_else(|| DEFAULT_STATUS_MODES.to_owned());
+
+        // Split "(modes)prefixes" into its parts
+        let parse = |status_modes: &str| -> Option<Vec<(char, char)>> {
+            let end = status_modes.find(')')?;
+            let modes = status_modes.get(1..end)?;
+            let prefixes = status_modes.get(end + 1..)?;
+            if !status_modes.starts_with('(') || modes.chars().count() != prefixes.chars().count() {
+                return None;
+            }
+            let mut status_modes: Vec<(char, char)> = modes.chars().zip(prefixes.chars()).filter(|(mode, _)| "qaohv".contains(*mode)).collect();
+            status_modes.sort_by_key(|(mode, _)| "qaohv".find(*mode));
+            Some(status_modes)
+        };
+
+        parse(&status_modes).unwrap_or_else(|| parse(DEFAULT_STATUS_MODES).unwrap())
+    }
+
+    /// Gets the maximum combined number of ban, exception and invite exception entries per channel.
+    pub fn get_max_list_entries(&self) -> usize {
+        self.channel.as_ref()
+            .and_then(|channel| channel.max_list_entries)
+            .unwrap_or(DEFAULT_MAX_LIST_ENTRIES)
+    }
+
+    /// Gets the password hash a client connecting from a host has to send with PASS, if any.
+    /// 
+    /// The password of the first `[[connection_class]]` matching the host
+    /// takes precedence over the server password.
+    pub fn get_password(&self, hostname: &str, casemapping: Casemapping) -> Option<&str> {
+        self.connection_class.as_ref()
+            .and_then(|classes| classes.iter().find(|class| crate::mask::matches(&class.host, hostname, casemapping)))
+            .and_then(|class| class.password.as_ref())
+            .or(self.server.password.as_ref())
+            .map(|password| password.as_ref())
+    }
+
+    /// Gets the maximum number of mode changes with a parameter in a single MODE command.
+    pub fn get_max_modes(&self) -> usize {
+        self.channel.as_ref()
+            .and_then(|channel| channel.max_modes)
+            .unwrap_or(DEFAULT_MAX_MODES)
+    }
+
+    /// Gets the maximum length of channel names.
+    pub fn get_channel_length(&self) -> usize {
+        self.channel.as_ref()
+            .and_then(|channel| channel.channel_length)
+            .unwrap_or(DEFAULT_CHANNEL_LENGTH)
+    }
+
+    /// Gets the maximum length of channel topics.
+    pub fn get_topic_length(&self) -> usize {
+        self.channel.as_ref()
+            .and_then(|channel| channel.topic_length)
+            .unwrap_or(DEFAULT_TOPIC_LENGTH)
+    }
+
+    /// Finds the `[[oper]]` block with the specified name.
+    pub fn get_oper(&self, name: &str) -> Option<&ServerConfigOper> {
+        self.oper.as_ref()?.iter().find(|oper| oper.name == name)
+    }
+
+    /// Gets the administrative information about the server, if configured.
+    pub fn get_admin(&self) -> Option<&ServerConfigAdmin> {
+        self.admin.as_ref()
+    }
+
+    /// Gets the privileges of an operator class.
+    /// 
+    /// Unknown classes don't grant any privileges.
+    pub fn get_oper_privileges(&self, class: &str) -> Vec<OperPrivilege> {
+        self.oper_class.as_ref()
+            .and_then(|classes| classes.iter().find(|oper_class| oper_class.name == class))
+            .map(|oper_class| oper_class.privileges.clone())
+            .unwrap_or_default()
+    }
 }
 
+/// The default name of the IRC network
+const DEFAULT_NETWORK: &str = "Zircon";
+
+/// The default number of seconds after which unregistered connections are closed
+const DEFAULT_REGISTRATION_TIMEOUT: u64 = 60;
+
+/// The default minimum number of seconds between two nickname changes
+const DEFAULT_NICK_CHANGE_DELAY: u64 = 5;
+
+/// The default path of the file containing the message of the day
+const DEFAULT_MOTD_FILE: &str = "motd.txt";
+
+/// The default number of seconds a client may be idle before it is sent a PING
+const DEFAULT_PING_INTERVAL: u64 = 120;
+
+/// The default number of seconds to wait for a reply to a PING
+const DEFAULT_PING_TIMEOUT: u64 = 60;
+
+/// The default maximum length of nicknames
+const DEFAULT_NICK_LENGTH: usize = 30;
+
+/// The default channel member status modes
+const DEFAULT_STATUS_MODES: &str = "(qaohv)~&@%+";
+
+/// The default maximum number of list mode entries per channel
+const DEFAULT_MAX_LIST_ENTRIES: usize = 100;
+
+/// The default maximum number of mode changes with a parameter per MODE command
+const DEFAULT_MAX_MODES: usize = 4;
+
+/// The default maximum length of channel names
+const DEFAULT_CHANNEL_LENGTH: usize = 50;
+
+/// The default maximum length of channel topics
+const DEFAULT_TOPIC_LENGTH: usize = 390;
+
 impl Default for ServerConfig {
     fn default() -> Self {
         Self {
             server: ServerConfigServer::default(),
             client: Some(ServerConfigClient::default()),
+            channel: Some(ServerConfigChannel::default()),
+            connection_class: None,
+            oper_class: None,
+            oper: None,
+            admin: None,
         }
     }
 }
@@ -56,6 +348,10 @@ impl Default for ServerConfigServer {
             listen: "127.0.0.1".to_string(),
             host: "127.0.0.1".to_string(),
             port: 6667,
+            network: Some(DEFAULT_NETWORK.to_owned()),
+            casemapping: Some(Casemapping::default()),
+            password: None,
+            motd_file: Some(DEFAULT_MOTD_FILE.to_owned()),
         }
     }
 }
@@ -64,6 +360,54 @@ impl Default for ServerConfigClient {
     fn default() -> Self {
         Self {
             autojoin: Some(vec!["#chat".to_owned()]),
+            nick_length: Some(DEFAULT_NICK_LENGTH),
+            registration_timeout: Some(DEFAULT_REGISTRATION_TIMEOUT),
+            nick_change_delay: Some(DEFAULT_NICK_CHANGE_DELAY),
+            ping_interval: Some(DEFAULT_PING_INTERVAL),
+            ping_timeout: Some(DEFAULT_PING_TIMEOUT),
         }
     }
+}
+
+impl Default for ServerConfigChannel {
+    fn default() -> Self {
+        Self {
+            status_modes: Some(DEFAULT_STATUS_MODES.to_owned()),
+            max_list_entries: Some(DEFAULT_MAX_LIST_ENTRIES),
+            max_modes: Some(DEFAULT_MAX_MODES),
+            channel_length: Some(DEFAULT_CHANNEL_LENGTH),
+            topic_length: Some(DEFAULT_TOPIC_LENGTH),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SERVER: &str = "[server]\nlisten = \"127.0.0.1\"\nhost = \"localhost\"\nport = 6667\n";
+
+    #[test]
+    fn test_oper_privileges() {
+        let config: ServerConfig = toml::from_str(&format!(
+            "{}[[oper_class]]\nname = \"admin\"\nprivileges = [\"kill\", \"kline\", \"rehash\", \"die\", \"override\"]\n",
+            SERVER
+        )).unwrap();
+        assert_eq!(config.get_oper_privileges("admin"), vec![
+            OperPrivilege::Kill,
+            OperPrivilege::Kline,
+            OperPrivilege::Rehash,
+            OperPrivilege::Die,
+            OperPrivilege::Override,
+        ]);
+        assert!(config.get_oper_privileges("unknown").is_empty());
+    }
+
+    #[test]
+    fn test_motd_file() {
+        let config: ServerConfig = toml::from_str(SERVER).unwrap();
+        assert_eq!(config.get_motd_file(), "motd.txt");
+        let config: ServerConfig = toml::from_str(&format!("{}motd_file = \"welcome.txt\"\n", SERVER)).unwrap();
+        assert_eq!(config.get_motd_file(), "welcome.txt");
+    }
 }
\ No newline at end of file
@@ -1,8 +1,10 @@
-// Macro for simple server-to-client communication
+// Macro for simple server-to-client communication.
+// The connection may already be broken, so errors are ignored here.
+// Broken connections are cleaned up once their reader notices.
 macro_rules! send {
-    ($writer:expr; $variant:expr) => {
-        std::io::Write::write_all($writer, format!("{}\r\n", $variant.to_string()).as_ref()).unwrap()
-    };
+    ($writer:expr; $variant:expr) => {{
+        std::io::Write::write_all($writer, format!("{}\r\n", $variant.to_string()).as_ref()).ok();
+    }};
 }
 
 pub fn dispatch(dispatcher: &impl CommandDispatch, mut server: &mut Server, mut client: &mut TcpStream, client_id: usize) {
@@ -12,6 +14,12 @@ pub fn dispatch(dispatcher: &impl CommandDispatch, mut server: &mut Server, mut
 mod command_dispatch;
 pub(crate) use self::command_dispatch::CommandDispatch;
 
+mod capability;
+pub(crate) use self::capability::Capability;
+
+mod register;
+pub(crate) use self::register::Register;
+
 mod set_nick;
 pub(crate) use self::set_nick::SetNick;
 
@@ -21,8 +29,77 @@ pub(crate) use self::join_channel::JoinChannel;
 mod part_channel;
 pub(crate) use self::part_channel::PartChannel;
 
+mod topic;
+pub(crate) use self::topic::Topic;
+
+mod invite;
+pub(crate) use self::invite::{Invite, ListInvites};
+
+mod kick;
+pub(crate) use self::kick::Kick;
+
+mod channel_mode;
+pub(crate) use self::channel_mode::ChannelMode;
+
+mod names;
+pub(crate) use self::names::Names;
+
+mod list;
+pub(crate) use self::list::List;
+
+mod who;
+pub(crate) use self::who::Who;
+
+mod user_mode;
+pub(crate) use self::user_mode::UserMode;
+
+mod whois;
+pub(crate) use self::whois::Whois;
+
+mod userhost;
+pub(crate) use self::userhost::{Userhost, Ison};
+
+mod whowas;
+pub(crate) use self::whowas::Whowas;
+
+mod away;
+pub(crate) use self::away::Away;
+
+mod motd;
+pub(crate) use self::motd::Motd;
+
+mod lusers;
+pub(crate) use self::lusers::Lusers;
+
+mod time;
+pub(crate) use self::time::Time;
+
+mod info;
+pub(crate) use self::info::Info;
+
+mod admin;
+pub(crate) use self::admin::Admin;
+
+mod version;
+pub(crate) use self::version::Version;
+
+mod oper;
+pub(crate) use self::oper::{Oper, Rehash, Die};
+
+mod kill;
+pub(crate) use self::kill::Kill;
+
 mod private_message;
 pub(crate) use self::private_message::PrivateMessage;
 
+mod keepalive;
+pub(crate) use self::keepalive::Keepalive;
+
+mod invalid_message;
+pub(crate) use self::invalid_message::InvalidMessage;
+
+mod quit;
+pub(crate) use self::quit::Quit;
+
 use std::net::TcpStream;
 use crate::server::Server;
\ No newline at end of file
@@ -0,0 +1,38 @@
+use std::net::TcpStream;
+
+use crate::dispatch::command_dispatch::CommandDispatch;
+
+use crate::server::Server;
+use crate::message::Respond;
+
+pub struct Admin {}
+
+impl CommandDispatch for Admin {
+    fn dispatch(&self, server: &mut Server, client: &mut TcpStream, client_id: usize) {
+
+        // Get the nickname of the current user
+        let nick = server.users.find(client_id).unwrap().nickname();
+        let host = server.config.get_host();
+
+        // Test whether administrative info is configured
+        let admin = match server.config.get_admin() {
+            Some(admin) => admin,
+            None => {
+                send!(client; Respond::to(host, &nick).err_no_admin_info());
+                return;
+            }
+        };
+
+        // Tell the client about the administrative info
+        send!(client; Respond::to(host, &nick).admin_me());
+        if let Some(location) = admin.location() {
+            send!(client; Respond::to(host, &nick).admin_location(location));
+        }
+        if let Some(organization) = admin.organization() {
+            send!(client; Respond::to(host, &nick).admin_organization(organization));
+        }
+        if let Some(email) = admin.email() {
+            send!(client; Respond::to(host, &nick).admin_email(email));
+        }
+    }
+}
\ No newline at end of file
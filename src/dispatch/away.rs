@@ -0,0 +1,47 @@
+use std::net::TcpStream;
+
+use crate::dispatch::command_dispatch::CommandDispatch;
+
+use crate::server::Server;
+use crate::message::Respond;
+
+pub struct Away {
+    pub message: Option<String>,
+}
+
+impl CommandDispatch for Away {
+    fn dispatch(&self, server: &mut Server, client: &mut TcpStream, client_id: usize) {
+
+        // Get the current user
+        let my_user = server.users.find_mut(client_id).unwrap();
+        let nick = my_user.nickname();
+        let hostmask = my_user.hostmask();
+        let host = server.config.get_host();
+
+        // An empty message marks the user as no longer away
+        let message = self.message.clone().filter(|message| !message.is_empty());
+        let was_away = my_user.away().is_some();
+        my_user.set_away(message.clone());
+
+        // Tell the client about the new state
+        if message.is_some() {
+            send!(client; Respond::to(host, &nick).now_away());
+        } else {
+            send!(client; Respond::to(host, &nick).unaway());
+        }
+
+        // Nothing changed for the other users
+        if !was_away && message.is_none() {
+            return;
+        }
+
+        // Tell all peers that enabled away-notify about the change
+        for peer_id in server.channels.peers(client_id) {
+            if let Some(other_user) = server.users.find_mut(peer_id) {
+                if other_user.has_capability("away-notify") {
+                    send!(other_user.stream(); Respond::to(&hostmask, &nick).away(message.clone()));
+                }
+            }
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,71 @@
+use std::net::TcpStream;
+
+use crate::dispatch::command_dispatch::CommandDispatch;
+
+use crate::server::Server;
+use crate::message::Respond;
+
+pub struct Capability {
+    pub subcommand: String,
+    pub capabilities: Option<String>,
+}
+
+impl Capability {
+
+    /// The IRCv3 capabilities supported by the server
+    pub const SUPPORTED: &'static [&'static str] = &["away-notify"];
+}
+
+impl CommandDispatch for Capability {
+    fn dispatch(&self, server: &mut Server, client: &mut TcpStream, client_id: usize) {
+
+        // Get the current user
+        // Clients may negotiate capabilities before choosing a nickname
+        let my_user = server.users.find_mut(client_id).unwrap();
+        let nick = if my_user.has_nickname() { my_user.nickname() } else { "*".to_owned() };
+        let host = server.config.get_host();
+
+        match self.subcommand.to_uppercase().as_ref() {
+
+            // List the supported capabilities
+            // The registration is suspended until the negotiation has ended
+            "LS" => {
+                my_user.set_negotiating(true);
+                send!(client; Respond::to(host, &nick).cap("LS", &Capability::SUPPORTED.join(" ")));
+            }
+
+            // List the enabled capabilities
+            "LIST" => {
+                send!(client; Respond::to(host, &nick).cap("LIST", &my_user.capabilities().join(" ")));
+            }
+
+            // Enable or disable capabilities
+            "REQ" => {
+                let requested = self.capabilities.clone().unwrap_or_default();
+                my_user.set_negotiating(true);
+
+                // The request is rejected as a whole if any capability is unknown
+                let is_supported = |capability: &str| Capability::SUPPORTED.contains(&capability.trim_start_matches('-'));
+                if requested.split_whitespace().all(is_supported) {
+                    for capability in requested.split_whitespace() {
+                        my_user.set_capability(capability.trim_start_matches('-'), !capability.starts_with('-'));
+                    }
+                    send!(client; Respond::to(host, &nick).cap("ACK", &requested));
+                } else {
+                    send!(client; Respond::to(host, &nick).cap("NAK", &requested));
+                }
+            }
+
+            // Negotiation has finished
+            "END" => {
+                my_user.set_negotiating(false);
+                super::dispatch(&super::Register {}, server, client, client_id);
+            }
+
+            // Unknown subcommand
+            _ => {
+                send!(client; Respond::to(host, &nick).err_invalid_cap_cmd(&self.subcommand));
+            }
+        }
+    }
+}
\ No newline at end of file
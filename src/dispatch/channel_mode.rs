@@ -0,0 +1,322 @@
+use std::net::TcpStream;
+
+use crate::dispatch::command_dispatch::CommandDispatch;
+
+use crate::server::{Server, Channel, ChannelModes, ListEntry};
+use crate::config::OperPrivilege;
+use crate::message::Respond;
+
+/// A single applied mode change.
+/// 
+/// * `0` - Whether the mode was set or unset
+/// * `1` - Mode character
+/// * `2` - Mode argument
+type ModeChange = (bool, char, Option<String>);
+
+pub struct ChannelMode {
+    pub channel_name: String,
+    pub modes: Option<String>,
+    pub arguments: Vec<String>,
+}
+
+impl ChannelMode {
+
+    /// Gets the status a member needs to set or unset a member status mode.
+    /// 
+    /// Half-operators may only give voice, operators may give voice
+    /// and half-operator status, all other modes require the mode itself.
+    fn required_rank(mode: char) -> char {
+        match mode {
+            'v' => 'h',
+            'h' => 'o',
+            mode => mode,
+        }
+    }
+
+    /// Tests whether a channel key is well-formed.
+    fn is_valid_key(key: &str) -> bool {
+        !key.is_empty() && !key.starts_with(':') && !key.contains(|chr: char| chr == ',' || chr.is_whitespace())
+    }
+
+    /// Tells the client about the entries of a list mode.
+    fn send_list(client: &mut TcpStream, host: &str, nick: &str, channel: &Channel, mode: char) {
+        for entry in channel.modes.list(mode) {
+            match mode {
+                'b' => send!(client; Respond::to(host, nick).ban_list(&channel.name, entry)),
+                'e' => send!(client; Respond::to(host, nick).exception_list(&channel.name, entry)),
+                _ => send!(client; Respond::to(host, nick).invite_exception_list(&channel.name, entry)),
+            }
+        }
+        match mode {
+            'b' => send!(client; Respond::to(host, nick).ban_list_end(&channel.name)),
+            'e' => send!(client; Respond::to(host, nick).exception_list_end(&channel.name)),
+            _ => send!(client; Respond::to(host, nick).invite_exception_list_end(&channel.name)),
+        }
+    }
+
+    /// Builds the mode string and mode arguments for a list of changes.
+    /// 
+    /// `[(true, 'n', None), (true, 'l', Some("10")), (false, 't', None)]` yields `+nl-t 10`.
+    fn format_changes(changes: &[ModeChange]) -> (String, Vec<String>) {
+        let mut modes = String::new();
+        let mut arguments = Vec::new();
+        let mut current_sign = None;
+        for (adding, mode, argument) in changes {
+            if current_sign != Some(*adding) {
+                modes.push(if *adding { '+' } else { '-' });
+                current_sign = Some(*adding);
+            }
+            modes.push(*mode);
+            if let Some(argument) = argument {
+                arguments.push(argument.clone());
+            }
+        }
+        (modes, arguments)
+    }
+}
+
+impl CommandDispatch for ChannelMode {
+    fn dispatch(&self, server: &mut Server, client: &mut TcpStream, client_id: usize) {
+
+        // Get the current user
+        let my_user = server.users.find(client_id).unwrap();
+        let nick = my_user.nickname();
+        let hostmask = my_user.hostmask();
+        let overrides = my_user.has_privilege(OperPrivilege::Override);
+        let host = server.config.get_host();
+        let status_modes = server.config.get_status_modes();
+        let is_status_mode = |mode: char| status_modes.iter().any(|(status_mode, _)| *status_mode == mode);
+        let max_list_entries = server.config.get_max_list_entries();
+        let casemapping = server.casemapping;
+        let max_modes = server.config.get_max_modes();
+
+        // Find the channel
+        let channel = match server.channels.find(&self.channel_name) {
+            Some(channel) => channel,
+            None => {
+
+                // Channel not found
+                // Notify the user about the error
+                send!(client; Respond::to(host, &nick).err_no_such_channel(self.channel_name.clone()));
+                return;
+            }
+        };
+
+        // Test whether the modes should be queried or changed
+        let modes = match &self.modes {
+            Some(modes) => modes,
+            None => {
+
+                // Tell the client about the current modes
+                // The key is only revealed to users in the channel
+                let (modes, arguments) = channel.modes.to_mode_string(channel.contains(client_id));
+                send!(client; Respond::to(host, &nick).channel_mode_is(&channel.name, &modes, &arguments));
+                send!(client; Respond::to(host, &nick).creation_time(&channel.name, channel.created));
+                return;
+            }
+        };
+
+        // List queries, such as `MODE #channel b`.
+        // Bans of secret and private channels are only shown to members, exceptions only to operators.
+        if self.arguments.is_empty() && modes.chars().all(|mode| mode == '+' || mode == '-' || ChannelModes::LIST_MODES.contains(mode)) {
+            let is_operator = overrides || channel.member(client_id).is_some_and(|member| member.is_operator());
+            for mode in modes.chars().filter(|mode| ChannelModes::LIST_MODES.contains(*mode)) {
+                match mode {
+                    'b' if channel.modes.is_hidden() && !channel.contains(client_id) && !overrides => {
+                        send!(client; Respond::to(host, &nick).err_not_on_channel(self.channel_name.clone()));
+                    }
+                    'e' | 'I' if !is_operator => {
+                        send!(client; Respond::to(host, &nick).err_chanop_privs_needed(&self.channel_name));
+                    }
+                    mode => ChannelMode::send_list(client, host, &nick, channel, mode),
+                }
+            }
+            return;
+        }
+
+        // Only users in the channel may change the channel modes
+        let member = match channel.member(client_id) {
+            Some(member) => member.clone(),
+            None => {
+
+                // Notify the user about the error
+                send!(client; Respond::to(host, &nick).err_chanop_privs_needed(&self.channel_name));
+                return;
+            }
+        };
+
+        // Apply the mode changes
+        let mut arguments = self.arguments.iter();
+        let mut adding = true;
+        let mut changes: Vec<ModeChange> = Vec::new();
+        let mut denied = false;
+        let mut parameter_modes = 0;
+        for mode in modes.chars() {
+
+            // Reject unknown modes before testing any privileges
+            let is_known = mode == '+' || mode == '-' || is_status_mode(mode)
+                || ChannelModes::LIST_MODES.contains(mode)
+                || ChannelModes::SIMPLE_MODES.contains(mode)
+                || ChannelModes::PARAMETER_MODES.contains(mode);
+            if !is_known {
+                send!(client; Respond::to(host, &nick).err_unknown_mode(mode));
+                continue;
+            }
+
+            // Test whether the mode consumes an argument.
+            // The key is given when it is removed as well, the limit isn't.
+            let takes_parameter = is_status_mode(mode) || ChannelModes::LIST_MODES.contains(mode) || mode == 'k' || (adding && mode == 'l');
+
+            // Ignore mode changes with a parameter beyond the limit, along with their argument
+            if takes_parameter {
+                parameter_modes += 1;
+                if parameter_modes > max_modes {
+                    arguments.next();
+                    continue;
+                }
+            }
+
+            // Test whether the user may change the mode
+            let allowed = match mode {
+                '+' | '-' => true,
+                _ if overrides => true,
+                mode if is_status_mode(mode) => member.has_rank(ChannelMode::required_rank(mode)),
+                _ => member.is_operator(),
+            };
+            if !allowed {
+
+                // Skip the argument of the mode
+                if takes_parameter {
+                    arguments.next();
+                }
+
+                // Notify the user about the error, but only once
+                if !denied {
+                    send!(client; Respond::to(host, &nick).err_chanop_privs_needed(&self.channel_name));
+                    denied = true;
+                }
+                continue;
+            }
+
+            match mode {
+                '+' => adding = true,
+                '-' => adding = false,
+
+                // Member status modes
+                mode if is_status_mode(mode) => match arguments.next() {
+                    Some(target_nick) => match server.users.find_by_name(target_nick) {
+                        Some(target_user) => match channel.member_mut(target_user.id) {
+                            Some(target_member) => {
+                                if target_member.set_status(mode, adding) {
+                                    changes.push((adding, mode, Some(target_user.nickname())));
+                                }
+                            }
+                            None => {
+                                send!(client; Respond::to(host, &nick).err_user_not_in_channel(&target_user.nickname(), &self.channel_name));
+                            }
+                        },
+                        None => {
+                            send!(client; Respond::to(host, &nick).err_no_such_nick(target_nick));
+                        }
+                    },
+                    None => {
+                        send!(client; Respond::to(host, &nick).err_need_more_params("MODE"));
+                    }
+                },
+
+                // List modes
+                mode if ChannelModes::LIST_MODES.contains(mode) => match arguments.next() {
+                    Some(mask) if adding => {
+                        let mask = crate::mask::normalize(mask);
+                        if channel.modes.list_entry_count() >= max_list_entries {
+                            send!(client; Respond::to(host, &nick).err_ban_list_full(&channel.name, mode));
+                        } else if channel.modes.add_to_list(mode, ListEntry::new(mask.clone(), hostmask.clone()), casemapping) {
+                            changes.push((true, mode, Some(mask)));
+                        }
+                    }
+                    Some(mask) => {
+                        let mask = crate::mask::normalize(mask);
+                        if let Some(entry) = channel.modes.remove_from_list(mode, &mask, casemapping) {
+                            changes.push((false, mode, Some(entry.mask)));
+                        }
+                    }
+                    None => ChannelMode::send_list(client, host, &nick, channel, mode),
+                },
+
+                // Modes without parameters
+                mode if ChannelModes::SIMPLE_MODES.contains(mode) => {
+                    if channel.modes.set_simple_mode(mode, adding) {
+                        changes.push((adding, mode, None));
+                    }
+                }
+
+                // Set the channel key
+                'k' if adding => match arguments.next() {
+                    Some(_) if channel.modes.key.is_some() => {
+                        send!(client; Respond::to(host, &nick).err_key_set(&channel.name));
+                    }
+                    Some(key) if ChannelMode::is_valid_key(key) => {
+                        channel.modes.key = Some(key.clone());
+                        changes.push((true, 'k', Some(key.clone())));
+                    }
+                    Some(_) => (),
+                    None => {
+                        send!(client; Respond::to(host, &nick).err_need_more_params("MODE"));
+                    }
+                },
+
+                // Remove the channel key
+                // The key argument is optional and ignored
+                'k' => {
+                    arguments.next();
+                    if channel.modes.key.take().is_some() {
+                        changes.push((false, 'k', Some("*".to_owned())));
+                    }
+                }
+
+                // Set the user limit
+                'l' if adding => match arguments.next() {
+                    Some(limit) => match limit.parse::<usize>() {
+                        Ok(limit) if limit > 0 && channel.modes.limit != Some(limit) => {
+                            channel.modes.limit = Some(limit);
+                            changes.push((true, 'l', Some(limit.to_string())));
+                        }
+                        _ => (),
+                    },
+                    None => {
+                        send!(client; Respond::to(host, &nick).err_need_more_params("MODE"));
+                    }
+                },
+
+                // Remove the user limit
+                'l' => {
+                    if channel.modes.limit.take().is_some() {
+                        changes.push((false, 'l', None));
+                    }
+                }
+
+                // Unknown mode
+                mode => {
+                    send!(client; Respond::to(host, &nick).err_unknown_mode(mode));
+                }
+            }
+        }
+
+        // Nothing to tell anyone if no mode has changed
+        if changes.is_empty() {
+            return;
+        }
+
+        // Relay the mode changes to all users in the channel
+        let (modes, arguments) = ChannelMode::format_changes(&changes);
+        for user_info in channel.users() {
+
+            // Find user by user id
+            if let Some(other_user) = server.users.find_mut(user_info.client_id()) {
+
+                // Tell the user's client about the mode changes
+                send!(other_user.stream(); Respond::to(&hostmask, &channel.name).mode(&modes, &arguments));
+            }
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,23 @@
+use std::net::TcpStream;
+
+use crate::dispatch::command_dispatch::CommandDispatch;
+
+use crate::server::Server;
+use crate::message::Respond;
+
+pub struct Info {}
+
+impl CommandDispatch for Info {
+    fn dispatch(&self, server: &mut Server, client: &mut TcpStream, client_id: usize) {
+
+        // Get the nickname of the current user
+        let nick = server.users.find(client_id).unwrap().nickname();
+        let host = server.config.get_host();
+
+        // Tell the client about the server software
+        send!(client; Respond::to(host, &nick).info(&format!("Zircon IRCd v{}", crate_version!())));
+        send!(client; Respond::to(host, &nick).info("Zircond is open source! Contribute here: https://github.com/splittydev/zircond"));
+        send!(client; Respond::to(host, &nick).info(&format!("On-line since {}", crate::time::format_timestamp(server.start_time))));
+        send!(client; Respond::to(host, &nick).info_end());
+    }
+}
\ No newline at end of file
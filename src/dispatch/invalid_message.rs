@@ -0,0 +1,40 @@
+use std::net::TcpStream;
+
+use crate::dispatch::command_dispatch::CommandDispatch;
+
+use crate::server::Server;
+use crate::message::Respond;
+use crate::parser::ParseError;
+
+/// Tells the client why a message couldn't be parsed.
+pub struct InvalidMessage {
+    pub error: ParseError,
+}
+
+impl CommandDispatch for InvalidMessage {
+    fn dispatch(&self, server: &mut Server, client: &mut TcpStream, client_id: usize) {
+
+        // Get the nickname of the current user, if there is one
+        let nick = match server.users.find(client_id) {
+            Some(user) if user.has_nickname() => user.nickname(),
+            Some(_) => "*".to_owned(),
+            None => return,
+        };
+        let response = Respond::to(server.config.get_host(), &nick);
+
+        // Send the matching error reply
+        match &self.error {
+            ParseError::EmptyMessage => (),
+            ParseError::MessageTooLong | ParseError::TagsTooLong => send!(client; response.err_input_too_long()),
+            ParseError::UnknownCommand(command) => send!(client; response.err_unknown_command(command)),
+            ParseError::NeedMoreParams(command) => send!(client; response.err_need_more_params(command)),
+            ParseError::NoNicknameGiven => send!(client; response.err_no_nickname_given()),
+            ParseError::NoOrigin => send!(client; response.err_no_origin()),
+
+            // NOTICE must never cause an automatic reply
+            ParseError::NoRecipient(command) | ParseError::NoTextToSend(command) if command == "NOTICE" => (),
+            ParseError::NoRecipient(command) => send!(client; response.err_no_recipient(command)),
+            ParseError::NoTextToSend(_) => send!(client; response.err_no_text_to_send()),
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,108 @@
+use std::net::TcpStream;
+
+use crate::dispatch::command_dispatch::CommandDispatch;
+
+use crate::server::Server;
+use crate::config::OperPrivilege;
+use crate::message::Respond;
+
+pub struct Invite {
+    pub nickname: String,
+    pub channel_name: String,
+}
+
+impl CommandDispatch for Invite {
+    fn dispatch(&self, server: &mut Server, client: &mut TcpStream, client_id: usize) {
+
+        // Get the current user
+        let my_user = server.users.find(client_id).unwrap();
+        let nick = my_user.nickname();
+        let hostmask = my_user.hostmask();
+        let overrides = my_user.has_privilege(OperPrivilege::Override);
+        let host = server.config.get_host();
+
+        // Find the channel
+        let channel = match server.channels.get(&self.channel_name) {
+            Some(channel) => channel,
+            None => {
+
+                // Channel not found
+                // Notify the user about the error
+                send!(client; Respond::to(host, &nick).err_no_such_channel(self.channel_name.clone()));
+                return;
+            }
+        };
+
+        // Test whether the user is on the channel
+        let member = match channel.member(client_id) {
+            Some(member) => member,
+            None => {
+
+                // Notify the user about the error
+                send!(client; Respond::to(host, &nick).err_not_on_channel(self.channel_name.clone()));
+                return;
+            }
+        };
+
+        // Only channel operators may invite users to invite-only channels
+        if channel.modes.invite_only && !overrides && !member.is_half_operator() {
+
+            // Notify the user about the error
+            send!(client; Respond::to(host, &nick).err_chanop_privs_needed(&channel.name));
+            return;
+        }
+
+        // Find the invited user
+        let target = match server.users.find_by_name(&self.nickname) {
+            Some(target) => target,
+            None => {
+
+                // Notify the user about the error
+                send!(client; Respond::to(host, &nick).err_no_such_nick(&self.nickname));
+                return;
+            }
+        };
+        let (target_id, target_nick) = (target.id, target.nickname());
+
+        // Test whether the invited user is already on the channel
+        if channel.contains(target_id) {
+
+            // Notify the user about the error
+            send!(client; Respond::to(host, &nick).err_user_on_channel(&target_nick, &channel.name));
+            return;
+        }
+
+        // Remember the invite
+        let channel_name = channel.name.clone();
+        let target = server.users.find_mut(target_id).unwrap();
+        target.add_invite(&channel_name, server.casemapping);
+
+        // Tell the invited user about the invite
+        send!(target.stream(); Respond::to(&hostmask, &target_nick).invite(&channel_name));
+
+        // Acknowledge the invite
+        send!(client; Respond::to(host, &nick).inviting(&target_nick, &channel_name));
+    }
+}
+
+pub struct ListInvites {
+    // No parameters
+}
+
+impl CommandDispatch for ListInvites {
+    fn dispatch(&self, server: &mut Server, client: &mut TcpStream, client_id: usize) {
+
+        // Get the current user
+        let my_user = server.users.find(client_id).unwrap();
+        let nick = my_user.nickname();
+        let host = server.config.get_host();
+
+        // Tell the client about all pending invites
+        for channel_name in my_user.invites() {
+            send!(client; Respond::to(host, &nick).invite_list(channel_name));
+        }
+
+        // Mark the end of the invite list
+        send!(client; Respond::to(host, &nick).invite_list_end());
+    }
+}
\ No newline at end of file
@@ -3,6 +3,7 @@ use std::net::TcpStream;
 use crate::dispatch::command_dispatch::CommandDispatch;
 
 use crate::server::{Server, Channel};
+use crate::name::ChannelName;
 use crate::message::Respond;
 
 pub struct JoinChannel {
@@ -15,6 +16,11 @@ impl CommandDispatch for JoinChannel {
 
         // Get the current user
         let my_user = server.users.find(client_id).unwrap();
+        let nick = my_user.nickname();
+        let hostmask = my_user.hostmask();
+
+        // Invited users may bypass +i, +k and +l
+        let is_invited = my_user.has_invite(&self.channel_name, server.casemapping);
 
         // Test whether the channel exists
         if let Some(channel) = server.channels.find(&self.channel_name) {
@@ -23,51 +29,68 @@ impl CommandDispatch for JoinChannel {
             if channel.contains(client_id) {
                 return; // just do nothing
             }
+
+            // Test whether the user may join the channel
+            let host = server.config.get_host();
+            if channel.modes.is_banned(&hostmask, server.casemapping) {
+                send!(client; Respond::to(host, &nick).err_banned_from_chan(&channel.name));
+                return;
+            }
+            if channel.modes.invite_only && !is_invited && !channel.modes.is_invite_exempt(&hostmask, server.casemapping) {
+                send!(client; Respond::to(host, &nick).err_invite_only_chan(&channel.name));
+                return;
+            }
+            if channel.modes.key.is_some() && !is_invited && channel.modes.key != self.channel_key {
+                send!(client; Respond::to(host, &nick).err_bad_channel_key(&channel.name));
+                return;
+            }
+            if let Some(limit) = channel.modes.limit {
+                if channel.users().len() >= limit && !is_invited {
+                    send!(client; Respond::to(host, &nick).err_channel_is_full(&channel.name));
+                    return;
+                }
+            }
         } else {
+
+            // Make sure the channel name is valid
+            let channel_name = match ChannelName::parse(&self.channel_name, server.config.get_channel_length()) {
+                Some(channel_name) => channel_name,
+                None => {
+
+                    // Notify the user about the error
+                    send!(client; Respond::to(server.config.get_host(), &nick).err_bad_chan_mask(&self.channel_name));
+                    return;
+                }
+            };
         
             // Create a new channel
-            let channel = Channel::new(self.channel_name.clone());
+            let channel = Channel::new(channel_name);
 
             // Add the new channel to the channel list
             server.channels.add(channel);
         }
 
+        // The invite has been used up
+        if is_invited {
+            server.users.find_mut(client_id).unwrap().remove_invite(&self.channel_name, server.casemapping);
+        }
+
         // Find the channel
         let channel = server.channels.find(&self.channel_name).unwrap();
 
+        let channel_name = channel.name.clone();
+
         // Add the user to the channel
         channel.join_user(client_id);
 
         // Send join acknowledgement to the user
-        let nick = my_user.nickname();
-        send!(client; Respond::to(&nick, &nick).join(self.channel_name.clone()));
+        send!(client; Respond::to(&nick, &nick).join(channel_name.clone()));
 
-        // Test whether the channel has a topic
-        if let Some(topic) = &channel.topic {
-        
-            // Tell the client about the topic
-            send!(client; Respond::to(&nick, &self.channel_name).topic(topic.clone()));
-        }
+        // Tell the client about the topic
+        super::Topic::send_topic(client, server.config.get_host(), &nick, channel, false);
 
-        // Iterate over all users in the channel
-        for user_info in channel.users() {
-            
-            // Find the user
-            if let Some(channel_user) = server.users.find(user_info.client_id()) {
-            
-                // Get channel mode
-                // "=": public
-                // "@": secret (+s)
-                // "*": private (+p)
-                let channel_mode = "=";
-                
-                // Tell the client about the user
-                send!(client; Respond::to(server.config.get_host(), &nick).names_reply(&self.channel_name, channel_mode, "", &channel_user.nickname()))
-            }
-        }
-
-        // Mark the end of the user list    
-        send!(client; Respond::to(server.config.get_host(), &nick).names_end(&self.channel_name));
+        // Get the away message of the user
+        let away = server.users.find(client_id).unwrap().away().map(|away| away.to_owned());
 
         // Iterate over all users in the channel
         for other_client in channel.users() {
@@ -81,8 +104,18 @@ impl CommandDispatch for JoinChannel {
             if let Some(other_user) = server.users.find_mut(other_client.client_id()) {
             
                 // Tell the user's client about the join
-                send!(other_user.stream(); Respond::to(&nick, &nick).join(self.channel_name.clone()));
+                send!(other_user.stream(); Respond::to(&nick, &nick).join(channel_name.clone()));
+
+                // Tell clients with away-notify that the user is away
+                if away.is_some() && other_user.has_capability("away-notify") {
+                    send!(other_user.stream(); Respond::to(&hostmask, &nick).away(away.clone()));
+                }
             }
         }
+
+        // Tell the client about the users in the channel
+        super::dispatch(&super::Names {
+            channel_names: Some(vec![channel_name]),
+        }, server, client, client_id);
     }
 }
\ No newline at end of file
@@ -0,0 +1,38 @@
+use std::net::TcpStream;
+
+use crate::dispatch::command_dispatch::CommandDispatch;
+
+use crate::server::Server;
+use crate::message::Respond;
+
+/// Probes an idle connection with a PING,
+/// or closes it if the previous PING wasn't answered in time.
+pub struct Keepalive {}
+
+impl CommandDispatch for Keepalive {
+    fn dispatch(&self, server: &mut Server, client: &mut TcpStream, client_id: usize) {
+        let ping_interval = server.config.get_ping_interval();
+        let ping_timeout = server.config.get_ping_timeout();
+
+        // The user might already be gone
+        let my_user = match server.users.find_mut(client_id) {
+            Some(user) => user,
+            None => return,
+        };
+        let idle_time = my_user.time_since_activity();
+
+        // Close the connection if the PING wasn't answered in time
+        if my_user.is_awaiting_pong() {
+            if idle_time >= ping_interval + ping_timeout {
+                let reason = format!("Ping timeout: {} seconds", idle_time);
+                super::Quit::disconnect(server, client_id, &reason, &reason);
+            }
+            return;
+        }
+
+        // Send a PING to the client
+        my_user.set_awaiting_pong();
+        let host = server.config.get_host();
+        send!(client; Respond::to(host, host).ping(host));
+    }
+}
\ No newline at end of file
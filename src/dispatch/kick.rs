@@ -0,0 +1,119 @@
+use std::net::TcpStream;
+
+use crate::dispatch::command_dispatch::CommandDispatch;
+
+use crate::server::Server;
+use crate::config::OperPrivilege;
+use crate::message::Respond;
+
+pub struct Kick {
+    pub channel_name: String,
+    pub nicknames: Vec<String>,
+    pub reason: Option<String>,
+}
+
+impl Kick {
+
+    /// The maximum number of users kicked by a single command
+    pub const MAX_TARGETS: usize = 4;
+}
+
+impl CommandDispatch for Kick {
+    fn dispatch(&self, server: &mut Server, client: &mut TcpStream, client_id: usize) {
+
+        // Get the current user
+        let my_user = server.users.find(client_id).unwrap();
+        let nick = my_user.nickname();
+        let hostmask = my_user.hostmask();
+        let overrides = my_user.has_privilege(OperPrivilege::Override);
+        let host = server.config.get_host();
+
+        // Get the KICK message
+        let reason = self.reason.clone().unwrap_or_else(|| nick.clone());
+
+        // Find the channel
+        let channel = match server.channels.find(&self.channel_name) {
+            Some(channel) => channel,
+            None => {
+
+                // Channel not found
+                // Notify the user about the error
+                send!(client; Respond::to(host, &nick).err_no_such_channel(self.channel_name.clone()));
+                return;
+            }
+        };
+
+        // Test whether the user is on the channel
+        let member = match channel.member(client_id) {
+            Some(member) => member.clone(),
+            None => {
+
+                // Notify the user about the error
+                send!(client; Respond::to(host, &nick).err_not_on_channel(self.channel_name.clone()));
+                return;
+            }
+        };
+
+        // Test whether the user may kick other users
+        if !overrides && !member.is_half_operator() {
+
+            // Notify the user about the error
+            send!(client; Respond::to(host, &nick).err_chanop_privs_needed(&self.channel_name));
+            return;
+        }
+
+        for (i, nickname) in self.nicknames.iter().enumerate() {
+
+            // Ignore all targets beyond the limit
+            if i >= Kick::MAX_TARGETS {
+                send!(client; Respond::to(host, &nick).err_too_many_targets(nickname));
+                break;
+            }
+
+            // Find the user
+            let target = match server.users.find_by_name(nickname) {
+                Some(target) => target,
+                None => {
+
+                    // Notify the user about the error
+                    send!(client; Respond::to(host, &nick).err_no_such_nick(nickname));
+                    continue;
+                }
+            };
+            let (target_id, target_nick) = (target.id, target.nickname());
+
+            // Test whether the user is on the channel
+            let target_member = match channel.member(target_id) {
+                Some(target_member) => target_member,
+                None => {
+
+                    // Notify the user about the error
+                    send!(client; Respond::to(host, &nick).err_user_not_in_channel(&target_nick, &self.channel_name));
+                    continue;
+                }
+            };
+
+            // Half-operators may not kick channel operators
+            if !overrides && !member.is_operator() && target_member.is_operator() {
+
+                // Notify the user about the error
+                send!(client; Respond::to(host, &nick).err_chanop_privs_needed(&self.channel_name));
+                continue;
+            }
+
+            // Relay the KICK to all users in the channel, including the kicked user
+            for user_info in channel.users() {
+
+                // Find user by user id
+                if let Some(other_user) = server.users.find_mut(user_info.client_id()) {
+
+                    // Tell the user's client about the KICK
+                    send!(other_user.stream(); Respond::to(&hostmask, &channel.name).kick(&target_nick, reason.clone()));
+                }
+            }
+
+            // Remove the user from the channel
+            channel.part_user(target_id);
+        }
+    }
+}
\ No newline at end of file
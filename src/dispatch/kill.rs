@@ -0,0 +1,53 @@
+use std::net::TcpStream;
+
+use crate::dispatch::command_dispatch::CommandDispatch;
+
+use crate::server::Server;
+use crate::config::OperPrivilege;
+use crate::message::Respond;
+
+pub struct Kill {
+    pub nickname: String,
+    pub reason: Option<String>,
+}
+
+impl CommandDispatch for Kill {
+    fn dispatch(&self, server: &mut Server, client: &mut TcpStream, client_id: usize) {
+
+        // Get the current user
+        let my_user = server.users.find(client_id).unwrap();
+        let nick = my_user.nickname();
+        let hostmask = my_user.hostmask();
+        let host = server.config.get_host().to_owned();
+
+        // Test whether the user may kill other users
+        if !my_user.has_privilege(OperPrivilege::Kill) {
+
+            // Notify the user about the error
+            send!(client; Respond::to(&host, &nick).err_no_privileges());
+            return;
+        }
+
+        // Find the target user
+        let target = match server.users.find_by_name_mut(&self.nickname) {
+            Some(target) => target,
+            None => {
+
+                // Notify the user about the error
+                send!(client; Respond::to(&host, &nick).err_no_such_nick(&self.nickname));
+                return;
+            }
+        };
+        let (target_id, target_nick) = (target.id, target.nickname());
+
+        // Get the KILL message
+        let reason = self.reason.clone().unwrap_or_else(|| nick.clone());
+        let message = format!("Killed ({} ({}))", nick, reason);
+
+        // Tell the target about the kill path
+        send!(target.stream(); Respond::to(&hostmask, &target_nick).kill(format!("{}!{} ({})", host, nick, reason)));
+
+        // Disconnect the target
+        super::Quit::disconnect(server, target_id, &message, &message);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,126 @@
+use std::net::TcpStream;
+
+use crate::dispatch::command_dispatch::CommandDispatch;
+
+use crate::server::{Server, Channel};
+use crate::casemapping::Casemapping;
+use crate::message::Respond;
+
+/// A single ELIST condition of a LIST query.
+enum ListFilter {
+
+    /// `>n` - More than `n` users
+    UsersAbove(usize),
+
+    /// `<n` - Fewer than `n` users
+    UsersBelow(usize),
+
+    /// `C<n` - Created less than `n` minutes ago
+    CreatedWithin(u64),
+
+    /// `C>n` - Created more than `n` minutes ago
+    CreatedBefore(u64),
+
+    /// `T<n` - Topic changed less than `n` minutes ago
+    TopicWithin(u64),
+
+    /// `T>n` - Topic changed more than `n` minutes ago
+    TopicBefore(u64),
+
+    /// `!mask` - Channel name doesn't match the mask
+    NotMask(String),
+
+    /// Channel name or mask
+    Mask(String),
+}
+
+impl ListFilter {
+
+    /// Parses a single filter. Malformed filters are treated as channel masks.
+    fn parse(filter: &str) -> ListFilter {
+        let number = |text: &str| text.parse::<u64>().ok();
+        let parsed = match filter.get(..2) {
+            Some("C<") => number(&filter[2..]).map(ListFilter::CreatedWithin),
+            Some("C>") => number(&filter[2..]).map(ListFilter::CreatedBefore),
+            Some("T<") => number(&filter[2..]).map(ListFilter::TopicWithin),
+            Some("T>") => number(&filter[2..]).map(ListFilter::TopicBefore),
+            _ if filter.starts_with('>') => number(&filter[1..]).map(|n| ListFilter::UsersAbove(n as usize)),
+            _ if filter.starts_with('<') => number(&filter[1..]).map(|n| ListFilter::UsersBelow(n as usize)),
+            _ if filter.starts_with('!') => Some(ListFilter::NotMask(filter[1..].to_owned())),
+            _ => None,
+        };
+        parsed.unwrap_or_else(|| ListFilter::Mask(filter.to_owned()))
+    }
+
+    /// Tests whether a channel satisfies the condition.
+    /// 
+    /// Channel masks are not tested here, since any of them may match.
+    fn matches(&self, channel: &Channel, casemapping: Casemapping) -> bool {
+        let minutes_since = |time: u64| crate::time::timestamp().saturating_sub(time) / 60;
+        match self {
+            ListFilter::UsersAbove(n) => channel.users().len() > *n,
+            ListFilter::UsersBelow(n) => channel.users().len() < *n,
+            ListFilter::CreatedWithin(n) => minutes_since(channel.created) < *n,
+            ListFilter::CreatedBefore(n) => minutes_since(channel.created) > *n,
+            ListFilter::TopicWithin(n) => channel.topic_time.is_some_and(|time| minutes_since(time) < *n),
+            ListFilter::TopicBefore(n) => channel.topic_time.is_some_and(|time| minutes_since(time) > *n),
+            ListFilter::NotMask(mask) => !crate::mask::matches(mask, &channel.name, casemapping),
+            ListFilter::Mask(_) => true,
+        }
+    }
+}
+
+pub struct List {
+    pub filters: Option<Vec<String>>,
+}
+
+impl List {
+
+    /// The supported ELIST extensions
+    pub const ELIST: &'static str = "CMNTU";
+}
+
+impl CommandDispatch for List {
+    fn dispatch(&self, server: &mut Server, client: &mut TcpStream, client_id: usize) {
+
+        // Get the current user
+        let nick = server.users.find(client_id).unwrap().nickname();
+        let host = server.config.get_host();
+        let casemapping = server.casemapping;
+
+        // Parse the filters
+        let filters: Vec<ListFilter> = self.filters.iter().flatten()
+            .filter(|filter| !filter.is_empty())
+            .map(|filter| ListFilter::parse(filter))
+            .collect();
+        let masks: Vec<&String> = filters.iter()
+            .filter_map(|filter| match filter { ListFilter::Mask(mask) => Some(mask), _ => None })
+            .collect();
+
+        // Mark the start of the channel list
+        send!(client; Respond::to(host, &nick).list_start());
+
+        for channel in server.channels.iter() {
+
+            // Skip hidden channels unless the user is in the channel
+            if channel.modes.is_hidden() && !channel.contains(client_id) {
+                continue;
+            }
+
+            // The channel has to match any of the masks and all other filters
+            if !masks.is_empty() && !masks.iter().any(|mask| crate::mask::matches(mask, &channel.name, casemapping)) {
+                continue;
+            }
+            if !filters.iter().all(|filter| filter.matches(channel, casemapping)) {
+                continue;
+            }
+
+            // Tell the client about the channel
+            let topic = channel.topic.as_ref().map_or("", |topic| topic.as_ref());
+            send!(client; Respond::to(host, &nick).list(&channel.name, channel.users().len(), topic));
+        }
+
+        // Mark the end of the channel list
+        send!(client; Respond::to(host, &nick).list_end());
+    }
+}
\ No newline at end of file
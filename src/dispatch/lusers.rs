@@ -0,0 +1,43 @@
+use std::net::TcpStream;
+
+use crate::dispatch::command_dispatch::CommandDispatch;
+
+use crate::server::Server;
+use crate::message::Respond;
+
+pub struct Lusers {}
+
+impl Lusers {
+
+    /// Tells the client about the number of users and channels on the server.
+    pub fn send_lusers(server: &Server, client: &mut TcpStream, nick: &str) {
+        let host = server.config.get_host();
+
+        // Count the users by their state
+        let registered: Vec<_> = server.users.iter().filter(|user| user.is_registered()).collect();
+        let users = registered.len();
+        let invisible = registered.iter().filter(|user| user.modes().invisible).count();
+        let operators = registered.iter().filter(|user| user.modes().operator).count();
+        let unknown = server.users.iter().count() - users;
+        let channels = server.channels.iter().count();
+
+        send!(client; Respond::to(host, nick).luser_client(users - invisible, invisible));
+        send!(client; Respond::to(host, nick).luser_op(operators));
+        send!(client; Respond::to(host, nick).luser_unknown(unknown));
+        send!(client; Respond::to(host, nick).luser_channels(channels));
+        send!(client; Respond::to(host, nick).luser_me(users));
+        send!(client; Respond::to(host, nick).local_users(users, server.max_users));
+        send!(client; Respond::to(host, nick).global_users(users, server.max_users));
+    }
+}
+
+impl CommandDispatch for Lusers {
+    fn dispatch(&self, server: &mut Server, client: &mut TcpStream, client_id: usize) {
+
+        // Get the nickname of the current user
+        let nick = server.users.find(client_id).unwrap().nickname();
+
+        // Tell the client about the users on the server
+        Lusers::send_lusers(server, client, &nick);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,74 @@
+use std::net::TcpStream;
+
+use crate::dispatch::command_dispatch::CommandDispatch;
+
+use crate::server::Server;
+use crate::message::Respond;
+
+pub struct Motd {}
+
+impl Motd {
+
+    /// Replaces the placeholders in a line of the message of the day.
+    fn fill_placeholders(server: &Server, nick: &str, line: &str) -> String {
+        let users = server.users.registered_count();
+        line.replace("{nick}", nick)
+            .replace("{network}", server.config.get_network())
+            .replace("{version}", &crate_version!())
+            .replace("{users}", &users.to_string())
+    }
+
+    /// Splits a line into parts that fit into the given number of bytes.
+    fn split_line(line: &str, max_len: usize) -> Vec<String> {
+        let mut parts = vec![String::new()];
+        for chr in line.chars() {
+            if parts.last().unwrap().len() + chr.len_utf8() > max_len {
+                parts.push(String::new());
+            }
+            parts.last_mut().unwrap().push(chr);
+        }
+        parts
+    }
+
+    /// Tells the client about the message of the day.
+    /// 
+    /// Long lines are split across as many RPL_MOTD lines
+    /// as needed to stay within the maximum message length.
+    pub fn send_motd(server: &Server, client: &mut TcpStream, nick: &str) {
+        let host = server.config.get_host();
+
+        // Test whether there is a message of the day
+        let motd = match &server.motd {
+            Some(motd) => motd,
+            None => {
+                send!(client; Respond::to(host, nick).err_no_motd());
+                return;
+            }
+        };
+
+        // Calculate the space available for text in a single line.
+        // The line looks like ":host 372 nick :- text\r\n"
+        let overhead = host.len() + nick.len() + 12;
+        let max_len = 512 - overhead;
+
+        send!(client; Respond::to(host, nick).motd_start());
+        for line in motd.lines() {
+            let line = Motd::fill_placeholders(server, nick, line);
+            for part in Motd::split_line(&line, max_len) {
+                send!(client; Respond::to(host, nick).motd(&part));
+            }
+        }
+        send!(client; Respond::to(host, nick).motd_end());
+    }
+}
+
+impl CommandDispatch for Motd {
+    fn dispatch(&self, server: &mut Server, client: &mut TcpStream, client_id: usize) {
+
+        // Get the nickname of the current user
+        let nick = server.users.find(client_id).unwrap().nickname();
+
+        // Tell the client about the message of the day
+        Motd::send_motd(server, client, &nick);
+    }
+}
\ No newline at end of file
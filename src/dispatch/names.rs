@@ -0,0 +1,131 @@
+use std::net::TcpStream;
+
+use crate::dispatch::command_dispatch::CommandDispatch;
+
+use crate::server::{Server, Channel, User};
+use crate::message::Respond;
+
+pub struct Names {
+    pub channel_names: Option<Vec<String>>,
+}
+
+impl Names {
+
+    /// Sends the nicknames of the channel members to the client.
+    /// 
+    /// The nicknames are split across as many RPL_NAMREPLY lines
+    /// as needed to stay within the maximum message length.
+    fn send_names(server: &Server, client: &mut TcpStream, nick: &str, channel_mode: &str, channel_name: &str, nicknames: &[String]) {
+
+        // Calculate the space available for nicknames in a single line.
+        // The line looks like ":host 353 nick = #channel :nicknames\r\n"
+        let host = server.config.get_host();
+        let overhead = host.len() + nick.len() + channel_mode.len() + channel_name.len() + 12;
+        let max_len = 512 - overhead;
+
+        // Pack the nicknames into lines
+        let mut line = String::new();
+        for nickname in nicknames {
+            if !line.is_empty() && line.len() + nickname.len() + 1 > max_len {
+                send!(client; Respond::to(host, nick).names_reply(channel_mode, channel_name, &line));
+                line.clear();
+            }
+            if !line.is_empty() {
+                line.push(' ');
+            }
+            line.push_str(nickname);
+        }
+
+        // Send the remaining nicknames
+        if !line.is_empty() {
+            send!(client; Respond::to(host, nick).names_reply(channel_mode, channel_name, &line));
+        }
+    }
+
+    /// Tests whether a channel is visible to a user.
+    /// 
+    /// Hidden channels are only visible to their members.
+    pub fn is_channel_visible(channel: &Channel, client_id: usize) -> bool {
+        !channel.modes.is_hidden() || channel.contains(client_id)
+    }
+
+    /// Gets all users in a channel that are visible to a user, along with their status prefix.
+    /// 
+    /// Invisible users are only included if the requesting user is in the channel.
+    /// This is shared by NAMES and WHO.
+    pub fn visible_members<'a>(server: &'a Server, channel: &Channel, client_id: usize) -> Vec<(&'a User, String)> {
+        let status_modes = server.config.get_status_modes();
+        let is_member = channel.contains(client_id);
+        channel.users().iter()
+            .filter_map(|user_info| {
+                server.users.find(user_info.client_id())
+                    .filter(|user| is_member || !user.modes().invisible)
+                    .map(|user| (user, user_info.prefix(&status_modes)))
+            })
+            .collect()
+    }
+
+    /// Gets the nicknames of all visible users in a channel, prefixed with their status.
+    fn channel_nicknames(server: &Server, channel: &Channel, client_id: usize) -> Vec<String> {
+        Names::visible_members(server, channel, client_id).iter()
+            .map(|(user, prefix)| format!("{}{}", prefix, user.nickname()))
+            .collect()
+    }
+}
+
+impl CommandDispatch for Names {
+    fn dispatch(&self, server: &mut Server, client: &mut TcpStream, client_id: usize) {
+
+        // Get the nickname of the current user
+        let nick = server.users.find(client_id).unwrap().nickname();
+
+        match &self.channel_names {
+
+            // List the users of specific channels
+            Some(channel_names) => {
+                for channel_name in channel_names {
+
+                    // Find the channel
+                    if let Some(channel) = server.channels.get(channel_name) {
+
+                        // Skip hidden channels unless the user is in the channel
+                        if Names::is_channel_visible(channel, client_id) {
+
+                            // Tell the client about the users
+                            let nicknames = Names::channel_nicknames(server, channel, client_id);
+                            Names::send_names(server, client, &nick, channel.modes.names_symbol(), &channel.name, &nicknames);
+                        }
+                    }
+
+                    // Mark the end of the user list
+                    send!(client; Respond::to(server.config.get_host(), &nick).names_end(channel_name));
+                }
+            }
+
+            // List the users of all channels
+            None => {
+                // Skip hidden channels unless the user is in the channel
+                let is_visible = |channel: &&Channel| Names::is_channel_visible(channel, client_id);
+
+                for channel in server.channels.iter().filter(is_visible) {
+
+                    // Tell the client about the users
+                    let nicknames = Names::channel_nicknames(server, channel, client_id);
+                    Names::send_names(server, client, &nick, channel.modes.names_symbol(), &channel.name, &nicknames);
+                }
+
+                // List all registered users that are not in any visible channel
+                let nicknames: Vec<String> = server.users.iter()
+                    .filter(|user| user.is_registered())
+                    .filter(|user| !user.modes().invisible || user.id == client_id)
+                    .filter(|user| !server.channels.iter().filter(is_visible).any(|channel| channel.contains(user.id)))
+                    .map(|user| user.nickname())
+                    .collect();
+                Names::send_names(server, client, &nick, "*", "*", &nicknames);
+
+                // Mark the end of the user list
+                send!(client; Respond::to(server.config.get_host(), &nick).names_end("*"));
+            }
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,132 @@
+use std::net::TcpStream;
+
+use crate::dispatch::command_dispatch::CommandDispatch;
+
+use crate::server::Server;
+use crate::config::OperPrivilege;
+use crate::message::Respond;
+
+pub struct Oper {
+    pub name: String,
+    pub password: String,
+}
+
+impl Oper {
+
+    /// The number of failed attempts after which the connection is closed
+    const MAX_FAILED_ATTEMPTS: u32 = 3;
+}
+
+impl CommandDispatch for Oper {
+    fn dispatch(&self, server: &mut Server, client: &mut TcpStream, client_id: usize) {
+
+        // Get the current user
+        let my_user = server.users.find(client_id).unwrap();
+        let nick = my_user.nickname();
+        let hostmask = my_user.hostmask();
+        let host = server.config.get_host();
+
+        // Find the operator block and verify the password.
+        // Unknown names get the same reply as wrong passwords, so they don't reveal which names exist.
+        let oper = match server.config.get_oper(&self.name).filter(|oper| oper.verify_password(&self.password)) {
+            Some(oper) => oper,
+            None => {
+
+                // Notify the user about the error
+                send!(client; Respond::to(host, &nick).err_passwd_mismatch());
+
+                // Close the connection after too many failed attempts
+                if server.users.find_mut(client_id).unwrap().add_failed_oper() >= Oper::MAX_FAILED_ATTEMPTS {
+                    super::Quit::disconnect(server, client_id, "Too many failed OPER attempts", "Too many failed OPER attempts");
+                }
+                return;
+            }
+        };
+
+        // Test whether the user connects from an allowed host
+        if let Some(mask) = oper.host() {
+            if !crate::mask::matches(&crate::mask::normalize(mask), &hostmask, server.casemapping) {
+
+                // Notify the user about the error
+                send!(client; Respond::to(host, &nick).err_no_oper_host());
+                return;
+            }
+        }
+
+        // Grant the privileges of the operator class
+        let privileges = server.config.get_oper_privileges(oper.class());
+        let my_user = server.users.find_mut(client_id).unwrap();
+        let was_operator = my_user.modes().operator;
+        my_user.set_operator(privileges);
+
+        // Tell the client about the new status
+        send!(client; Respond::to(host, &nick).youre_oper());
+        if !was_operator {
+            send!(client; Respond::to(&nick, &nick).mode("+o", &[]));
+        }
+    }
+}
+
+pub struct Rehash {}
+
+impl CommandDispatch for Rehash {
+    fn dispatch(&self, server: &mut Server, client: &mut TcpStream, client_id: usize) {
+
+        // Get the current user
+        let my_user = server.users.find(client_id).unwrap();
+        let nick = my_user.nickname();
+
+        // Test whether the user may reload the configuration
+        if !my_user.has_privilege(OperPrivilege::Rehash) {
+
+            // Notify the user about the error
+            send!(client; Respond::to(server.config.get_host(), &nick).err_no_privileges());
+            return;
+        }
+
+        // Tell the client that the configuration is being reloaded
+        send!(client; Respond::to(server.config.get_host(), &nick).rehashing(&server.config_path));
+
+        // Reload the configuration from the file the server was started with
+        match crate::read_config(&server.config_path) {
+            Ok(config) => server.set_config(config),
+            Err(err) => {
+
+                // Keep the current configuration
+                send!(client; Respond::to(server.config.get_host(), &nick).notice(format!("Rehash failed: {}", err)));
+            }
+        }
+    }
+}
+
+pub struct Die {}
+
+impl CommandDispatch for Die {
+    fn dispatch(&self, server: &mut Server, client: &mut TcpStream, client_id: usize) {
+
+        // Get the current user
+        let my_user = server.users.find(client_id).unwrap();
+        let nick = my_user.nickname();
+
+        // Test whether the user may shut down the server
+        if !my_user.has_privilege(OperPrivilege::Die) {
+
+            // Notify the user about the error
+            send!(client; Respond::to(server.config.get_host(), &nick).err_no_privileges());
+            return;
+        }
+
+        // Close all connections
+        let host = server.config.get_host().to_owned();
+        for user in server.users.iter_mut() {
+            let nick = user.nickname();
+            let error = Respond::to(&host, &nick).error(format!("Closing Link: {} (Server shutting down)", user.hostname()));
+            send!(user.stream(); error);
+            user.stream().shutdown(std::net::Shutdown::Both).ok();
+        }
+
+        // Save the current state and exit
+        server.save_state().unwrap();
+        std::process::exit(0);
+    }
+}
\ No newline at end of file
@@ -36,7 +36,7 @@ impl CommandDispatch for PartChannel {
             channel.part_user(client_id);
 
             // Notify the user about the PART
-            send!(client; Respond::to(&nick, &nick).part(self.channel_name.clone(), reason.clone()));
+            send!(client; Respond::to(&nick, &nick).part(channel.name.clone(), reason.clone()));
 
             // Relay the PART message to all other users in the channel
             for other_client in channel.users() {
@@ -50,7 +50,7 @@ impl CommandDispatch for PartChannel {
                 if let Some(other_user) = server.users.find_mut(other_client.client_id()) {
                 
                     // Tell the user's client about the PART
-                    send!(other_user.stream(); Respond::to(&nick, &nick).part(self.channel_name.clone(), reason.clone()));
+                    send!(other_user.stream(); Respond::to(&nick, &nick).part(channel.name.clone(), reason.clone()));
                 }
             }
         } else {
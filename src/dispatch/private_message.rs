@@ -3,18 +3,42 @@ use std::net::TcpStream;
 use crate::dispatch::command_dispatch::CommandDispatch;
 
 use crate::server::Server;
-use crate::message::Respond;
+use crate::message::{Respond, ResponseBuilder};
 
+/// Delivers a PRIVMSG or NOTICE to a channel or user.
+/// 
+/// Notices must never trigger automatic replies, so no
+/// error replies are sent back for them.
 pub struct PrivateMessage {
     pub target: String,
     pub message: String,
+    pub notice: bool,
+}
+
+impl PrivateMessage {
+
+    /// Builds the message line relayed to the recipients.
+    fn relay<'a>(&'a self, source: &'a str) -> ResponseBuilder<'a> {
+        let response = Respond::to(source, &self.target);
+        if self.notice {
+            response.notice(self.message.clone())
+        } else {
+            response.privmsg(self.message.clone())
+        }
+    }
 }
 
 impl CommandDispatch for PrivateMessage {
-    fn dispatch(&self, server: &mut Server, _client: &mut TcpStream, client_id: usize) {
+    fn dispatch(&self, server: &mut Server, client: &mut TcpStream, client_id: usize) {
 
         // Get the nickname of the current user
         let user_nick = server.users.find(client_id).unwrap().nickname();
+        let hostmask = server.users.find(client_id).unwrap().hostmask();
+
+        // Sending a message resets the idle time
+        if !self.notice {
+            server.users.find_mut(client_id).unwrap().reset_idle_time();
+        }
 
         // Determine whether the target is a user or a channel
         if self.target.starts_with('#') {
@@ -22,6 +46,17 @@ impl CommandDispatch for PrivateMessage {
             // Find the channel
             if let Some(channel) = server.channels.find(&self.target) {
 
+                // Test whether the user may send messages to the channel
+                let member = channel.member(client_id);
+                let can_speak = member.is_some_and(|member| member.can_speak());
+                let is_banned = channel.modes.is_banned(&hostmask, server.casemapping);
+                if (channel.modes.no_external_messages && member.is_none()) || ((channel.modes.moderated || is_banned) && !can_speak) {
+                    if !self.notice {
+                        send!(client; Respond::to(server.config.get_host(), &user_nick).err_cannot_send_to_chan(&self.target));
+                    }
+                    return;
+                }
+
                 // Find all users in the channel
                 for other_user_info in channel.users() {
 
@@ -33,15 +68,35 @@ impl CommandDispatch for PrivateMessage {
                     // Find the user
                     if let Some(other_user) = server.users.find_mut(other_user_info.client_id()) {
 
-                        // Relay the private message to the other user
-                        send!(other_user.stream(); Respond::to(&user_nick, &self.target).privmsg(self.message.clone()));
+                        // Deaf users receive no channel messages
+                        if other_user.modes().deaf {
+                            continue;
+                        }
+
+                        // Relay the message to the other user
+                        send!(other_user.stream(); self.relay(&user_nick));
                     }
                 }
+            } else if !self.notice {
+
+                // Channel not found
+                // Notify the user about the error
+                send!(client; Respond::to(server.config.get_host(), &user_nick).err_no_such_nick(&self.target));
             }
         } else if let Some(other_user) = server.users.find_by_name_mut(&self.target) {
 
-            // Send the private message to the other user
-            send!(other_user.stream(); Respond::to(&user_nick, &self.target).privmsg(self.message.clone()));
+            // Send the message to the other user
+            send!(other_user.stream(); self.relay(&user_nick));
+
+            // Tell the client if the other user is away
+            if let (false, Some(away)) = (self.notice, other_user.away()) {
+                send!(client; Respond::to(server.config.get_host(), &user_nick).away_reply(&other_user.nickname(), away));
+            }
+        } else if !self.notice {
+
+            // User not found
+            // Notify the user about the error
+            send!(client; Respond::to(server.config.get_host(), &user_nick).err_no_such_nick(&self.target));
         }
     }
 }
\ No newline at end of file
@@ -0,0 +1,76 @@
+use std::net::TcpStream;
+
+use crate::dispatch::command_dispatch::CommandDispatch;
+
+use crate::server::Server;
+use crate::message::Respond;
+
+pub struct Quit {
+    pub message: Option<String>,
+}
+
+impl Quit {
+
+    /// Disconnects a user from the server.
+    /// 
+    /// Relays the QUIT message to all users sharing a channel with the user,
+    /// closes the link with an `ERROR` message and remembers the nickname.
+    /// This is used both for QUIT and for connections closed by the server.
+    pub fn disconnect(server: &mut Server, client_id: usize, reason: &str, error: &str) {
+
+        // The user might already be gone if the connection was closed by the server
+        let (nick, hostmask) = match server.users.find(client_id) {
+            Some(user) => (user.nickname(), user.hostmask()),
+            None => return,
+        };
+
+        // Collect every user sharing at least one channel with the current user
+        let mut peers: Vec<usize> = Vec::new();
+        for channel in server.channels.iter_mut().filter(|channel| channel.contains(client_id)) {
+
+            // Remove the user from the channel
+            channel.part_user(client_id);
+
+            // Remember the remaining users, without duplicates
+            for user_info in channel.users() {
+                if !peers.contains(&user_info.client_id()) {
+                    peers.push(user_info.client_id());
+                }
+            }
+        }
+
+        // Relay the QUIT message to all peers exactly once
+        for peer_id in peers {
+            if let Some(other_user) = server.users.find_mut(peer_id) {
+                send!(other_user.stream(); Respond::to(&hostmask, &nick).quit(reason.to_owned()));
+            }
+        }
+
+        // Tell the client that the link is being closed
+        let user = server.users.find_mut(client_id).unwrap();
+        let error = Respond::to(server.config.get_host(), &nick).error(format!("Closing Link: {} ({})", user.hostname(), error));
+        send!(user.stream(); error);
+
+        // Close the connection
+        user.stream().shutdown(std::net::Shutdown::Both).ok();
+
+        // Remember the nickname of the user
+        if user.is_registered() {
+            server.whowas.add(user);
+        }
+
+        // Disconnect the user
+        server.users.disconnect(client_id);
+    }
+}
+
+impl CommandDispatch for Quit {
+    fn dispatch(&self, server: &mut Server, _client: &mut TcpStream, client_id: usize) {
+
+        // Get the QUIT message
+        let reason = self.message.clone().unwrap_or_else(|| "Connection closed".to_owned());
+
+        // Disconnect the user
+        Quit::disconnect(server, client_id, &reason, &reason);
+    }
+}
\ No newline at end of file
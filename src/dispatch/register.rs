@@ -0,0 +1,68 @@
+use std::net::TcpStream;
+
+use crate::dispatch::command_dispatch::CommandDispatch;
+
+use crate::server::{Server, ChannelModes, UserModes};
+use crate::message::Respond;
+
+/// Completes the registration of a user once it is ready,
+/// sending the welcome burst and joining the autojoin channels.
+pub struct Register {}
+
+impl CommandDispatch for Register {
+    fn dispatch(&self, mut server: &mut Server, mut client: &mut TcpStream, client_id: usize) {
+
+        // Test whether the registration can be completed
+        let my_user = server.users.find_mut(client_id).unwrap();
+        if !my_user.can_register() {
+            return;
+        }
+
+        // Verify the connection password
+        if let Some(hash) = server.config.get_password(my_user.hostname(), server.casemapping) {
+            if !my_user.password().is_some_and(|password| crate::config::verify_password(hash, password)) {
+
+                // Notify the user about the error and close the connection
+                send!(client; Respond::to(server.config.get_host(), &my_user.nickname()).err_passwd_mismatch());
+                super::Quit::disconnect(server, client_id, "Bad password", "Bad password");
+                return;
+            }
+        }
+
+        // Complete the registration
+        my_user.complete_registration();
+        let nick = &my_user.nickname();
+
+        // Remember the highest number of users seen
+        server.max_users = server.max_users.max(server.users.registered_count());
+
+        // Send the welcome sequence
+        send!(client; Respond::to(server.config.get_host(), nick).welcome(format!("Welcome, {}!", nick)));
+        send!(client; Respond::to(server.config.get_host(), nick).your_host(format!("Your host is {}, running Zircond.", server.config.get_host())));
+        send!(client; Respond::to(server.config.get_host(), nick).rpl_created(&crate::time::format_timestamp(server.start_time)));
+        let (channel_modes, parameter_modes) = ChannelModes::supported_modes(&server.config.get_status_modes());
+        send!(client; Respond::to(server.config.get_host(), nick).my_info(&format!("zircond-{}", crate_version!()), UserModes::SUPPORTED_MODES, &channel_modes, &parameter_modes));
+        super::Version::send_isupport(server, client, nick);
+        super::Lusers::send_lusers(server, client, nick);
+        super::Motd::send_motd(server, client, nick);
+
+        // Join autojoin channels
+        if let Some(channels) = server.config.get_autojoin_channels() {
+            for channel in channels {
+
+                // Make sure to not join any channels the user is already a part of
+                if let Some(existing_channel) = server.channels.find(&channel) {
+                    if existing_channel.contains(client_id) {
+                        continue;
+                    }
+                }
+
+                // Join the channel
+                super::dispatch(&crate::dispatch::JoinChannel {
+                    channel_name: channel,
+                    channel_key: None,
+                }, &mut server, &mut client, client_id);
+            }
+        }
+    }
+}
\ No newline at end of file
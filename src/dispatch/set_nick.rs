@@ -3,6 +3,7 @@ use std::net::TcpStream;
 use crate::dispatch::command_dispatch::CommandDispatch;
 
 use crate::server::Server;
+use crate::name::Nickname;
 use crate::message::Respond;
 
 pub struct SetNick {
@@ -10,46 +11,69 @@ pub struct SetNick {
 }
 
 impl CommandDispatch for SetNick {
-    fn dispatch(&self, mut server: &mut Server, mut client: &mut TcpStream, client_id: usize) {
+    fn dispatch(&self, server: &mut Server, client: &mut TcpStream, client_id: usize) {
+
+        // Get the current user
+        let user = server.users.find(client_id).unwrap();
+        let current_nick = if user.has_nickname() { user.nickname() } else { "*".to_owned() };
+        let hostmask = user.hostmask();
+        let is_registered = user.is_registered();
+
+        // Nothing to do if the nickname stays the same
+        if user.has_nickname() && current_nick == self.nickname {
+            return;
+        }
+
+        // Make sure the nickname is valid
+        let nickname = match Nickname::parse(&self.nickname, server.config.get_nick_length()) {
+            Some(nickname) => nickname,
+            None => {
+
+                // Notify the user about the error
+                send!(client; Respond::to(server.config.get_host(), &current_nick).err_erroneous_nickname(&self.nickname));
+                return;
+            }
+        };
 
         // Look for nickname collisions
-        if server.users.find_by_name(&self.nickname).is_some() {
+        // Changing the case of the current nickname is allowed
+        if server.users.find_by_name(&self.nickname).is_some_and(|other_user| other_user.id != client_id) {
 
             // Report name collision
-            let current_nick = server.users.find(client_id).unwrap().nickname();
             send!(client; Respond::to(server.config.get_host(), &current_nick).err_nickname_in_use(self.nickname.clone()));
+            return;
+        }
+
+        // Registered users may only change their nickname every few seconds
+        let wait_time = server.config.get_nick_change_delay().saturating_sub(user.time_since_nick_change());
+        if is_registered && wait_time > 0 {
+
+            // Notify the user about the error
+            send!(client; Respond::to(server.config.get_host(), &current_nick).err_nick_too_fast(&self.nickname, wait_time));
+            return;
+        }
+
+        // Remember the previous nickname
+        if is_registered {
+            server.whowas.add(user);
+        }
+
+        // Set the nickname
+        server.users.find_mut(client_id).unwrap().set_nickname(nickname);
+
+        // Complete the registration if USER has been received
+        if !is_registered {
+            super::dispatch(&super::Register {}, server, client, client_id);
+            return;
+        }
+
+        // Tell the client about the new nickname
+        send!(client; Respond::to(&hostmask, &self.nickname).nick());
 
-        } else {
-
-            // Set the nickname
-            server.users.find_mut(client_id).unwrap().set_nickname(self.nickname.clone());
-
-            // Send the welcome sequence
-            let nick = &self.nickname;
-            send!(client; Respond::to(server.config.get_host(), nick).welcome(format!("Welcome, {}!", nick)));
-            send!(client; Respond::to(server.config.get_host(), nick).your_host(format!("Your host is {}, running Zircond.", server.config.get_host())));
-            send!(client; Respond::to(server.config.get_host(), nick).motd_start());
-            send!(client; Respond::to(server.config.get_host(), nick).motd(&format!("Zircon IRCd v{}", &crate_version!())));
-            send!(client; Respond::to(server.config.get_host(), nick).motd("Zircond is open source! Contribute here: https://github.com/splittydev/zircond"));
-            send!(client; Respond::to(server.config.get_host(), &nick).motd_end());
-
-            // Join autojoin channels
-            if let Some(channels) = server.config.get_autojoin_channels() {
-                for channel in channels {
-
-                    // Make sure to not join any channels the user is already a part of
-                    if let Some(existing_channel) = server.channels.find(&channel) {
-                        if existing_channel.contains(client_id) {
-                            continue;
-                        }
-                    }
-
-                    // Join the channel
-                    super::dispatch(&crate::dispatch::JoinChannel {
-                        channel_name: channel,
-                        channel_key: None,
-                    }, &mut server, &mut client, client_id);
-                }
+        // Relay the NICK change to all peers exactly once
+        for peer_id in server.channels.peers(client_id) {
+            if let Some(other_user) = server.users.find_mut(peer_id) {
+                send!(other_user.stream(); Respond::to(&hostmask, &self.nickname).nick());
             }
         }
     }
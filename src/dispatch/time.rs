@@ -0,0 +1,20 @@
+use std::net::TcpStream;
+
+use crate::dispatch::command_dispatch::CommandDispatch;
+
+use crate::server::Server;
+use crate::message::Respond;
+
+pub struct Time {}
+
+impl CommandDispatch for Time {
+    fn dispatch(&self, server: &mut Server, client: &mut TcpStream, client_id: usize) {
+
+        // Get the nickname of the current user
+        let nick = server.users.find(client_id).unwrap().nickname();
+
+        // Tell the client about the local time of the server
+        let time = crate::time::format_timestamp(crate::time::timestamp());
+        send!(client; Respond::to(server.config.get_host(), &nick).time(&time));
+    }
+}
\ No newline at end of file
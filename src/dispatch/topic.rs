@@ -0,0 +1,117 @@
+use std::net::TcpStream;
+
+use crate::dispatch::command_dispatch::CommandDispatch;
+
+use crate::server::{Server, Channel};
+use crate::config::OperPrivilege;
+use crate::message::Respond;
+
+pub struct Topic {
+    pub channel_name: String,
+    pub topic: Option<String>,
+}
+
+impl Topic {
+
+    /// Tells the client about the topic of a channel.
+    /// 
+    /// Sends RPL_TOPIC followed by RPL_TOPICWHOTIME if the channel has a topic.
+    /// Nothing is sent if there is no topic, unless `reply_no_topic` is set.
+    pub fn send_topic(client: &mut TcpStream, host: &str, nick: &str, channel: &Channel, reply_no_topic: bool) {
+
+        // Test whether the channel has a topic
+        if let Some(topic) = &channel.topic {
+
+            // Tell the client about the topic
+            send!(client; Respond::to(host, nick).topic_reply(&channel.name, topic));
+
+            // Tell the client who set the topic and when
+            if let (Some(setter), Some(time)) = (&channel.topic_setter, channel.topic_time) {
+                send!(client; Respond::to(host, nick).topic_who_time(&channel.name, setter, time));
+            }
+        } else if reply_no_topic {
+
+            // Tell the client that there is no topic
+            send!(client; Respond::to(host, nick).no_topic(&channel.name));
+        }
+    }
+}
+
+impl CommandDispatch for Topic {
+    fn dispatch(&self, server: &mut Server, client: &mut TcpStream, client_id: usize) {
+
+        // Get the current user
+        let my_user = server.users.find(client_id).unwrap();
+        let nick = my_user.nickname();
+        let hostmask = my_user.hostmask();
+        let overrides = my_user.has_privilege(OperPrivilege::Override);
+
+        // Find the channel
+        let channel = match server.channels.find(&self.channel_name) {
+            Some(channel) => channel,
+            None => {
+
+                // Channel not found
+                // Notify the user about the error
+                send!(client; Respond::to(server.config.get_host(), &nick).err_no_such_channel(self.channel_name.clone()));
+                return;
+            }
+        };
+
+        // Test whether the topic should be queried or changed
+        let topic = match &self.topic {
+            Some(topic) => topic,
+            None => {
+
+                // The topic of hidden channels is only visible to users in the channel
+                if channel.modes.is_hidden() && !channel.contains(client_id) {
+                    send!(client; Respond::to(server.config.get_host(), &nick).err_not_on_channel(self.channel_name.clone()));
+                    return;
+                }
+
+                // Tell the client about the current topic
+                Topic::send_topic(client, server.config.get_host(), &nick, channel, true);
+                return;
+            }
+        };
+
+        // Test whether the user is on the channel
+        if !channel.contains(client_id) {
+
+            // Notify the user about the error
+            send!(client; Respond::to(server.config.get_host(), &nick).err_not_on_channel(self.channel_name.clone()));
+            return;
+        }
+
+        // Test whether the user may change the topic
+        if channel.modes.protected_topic && !overrides && !channel.member(client_id).unwrap().is_half_operator() {
+
+            // Notify the user about the error
+            send!(client; Respond::to(server.config.get_host(), &nick).err_chanop_privs_needed(&self.channel_name));
+            return;
+        }
+
+        // Set the new topic.
+        // An empty topic clears the current one.
+        // Topics that are too long are truncated to TOPICLEN bytes without splitting a character.
+        let mut topic = topic.clone();
+        let mut topic_length = topic.len().min(server.config.get_topic_length());
+        while !topic.is_char_boundary(topic_length) {
+            topic_length -= 1;
+        }
+        topic.truncate(topic_length);
+        let new_topic = if topic.is_empty() { None } else { Some(topic.clone()) };
+        channel.set_topic(new_topic, hostmask.clone());
+
+        // Relay the TOPIC change to all users in the channel
+        for user_info in channel.users() {
+
+            // Find user by user id
+            if let Some(other_user) = server.users.find_mut(user_info.client_id()) {
+
+                // Tell the user's client about the new topic
+                send!(other_user.stream(); Respond::to(&hostmask, &channel.name).topic(topic.clone()));
+            }
+        }
+    }
+}
\ No newline at end of file
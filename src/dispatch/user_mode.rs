@@ -0,0 +1,83 @@
+use std::net::TcpStream;
+
+use crate::dispatch::command_dispatch::CommandDispatch;
+
+use crate::server::{Server, UserModes};
+use crate::message::Respond;
+
+pub struct UserMode {
+    pub nickname: String,
+    pub modes: Option<String>,
+}
+
+impl CommandDispatch for UserMode {
+    fn dispatch(&self, server: &mut Server, client: &mut TcpStream, client_id: usize) {
+
+        // Get the current user
+        let my_user = server.users.find_mut(client_id).unwrap();
+        let nick = my_user.nickname();
+        let host = server.config.get_host();
+
+        // Users may only query and change their own modes
+        if !server.casemapping.equals(&nick, &self.nickname) {
+
+            // Notify the user about the error
+            if server.users.find_by_name(&self.nickname).is_some() {
+                send!(client; Respond::to(host, &nick).err_users_dont_match());
+            } else {
+                send!(client; Respond::to(host, &nick).err_no_such_nick(&self.nickname));
+            }
+            return;
+        }
+
+        // Test whether the modes should be queried or changed
+        let modes = match &self.modes {
+            Some(modes) => modes,
+            None => {
+
+                // Tell the client about the current modes
+                send!(client; Respond::to(host, &nick).user_mode_is(&my_user.modes().to_mode_string()));
+                return;
+            }
+        };
+
+        // Apply the mode changes
+        let mut adding = true;
+        let mut changes = String::new();
+        let mut current_sign = None;
+        let mut unknown_mode = false;
+        for mode in modes.chars() {
+            match mode {
+                '+' => adding = true,
+                '-' => adding = false,
+
+                // Unknown mode
+                mode if !UserModes::SUPPORTED_MODES.contains(mode) => unknown_mode = true,
+
+                // Modes that can only be set by the server, but can be given up
+                mode if adding && UserModes::SERVER_MODES.contains(mode) => (),
+
+                // Regular modes
+                mode => {
+                    if my_user.modes_mut().set_mode(mode, adding) {
+                        if current_sign != Some(adding) {
+                            changes.push(if adding { '+' } else { '-' });
+                            current_sign = Some(adding);
+                        }
+                        changes.push(mode);
+                    }
+                }
+            }
+        }
+
+        // Notify the user about unknown modes, but only once
+        if unknown_mode {
+            send!(client; Respond::to(host, &nick).err_umode_unknown_flag());
+        }
+
+        // Tell the client about the mode changes
+        if !changes.is_empty() {
+            send!(client; Respond::to(&nick, &nick).mode(&changes, &[]));
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,79 @@
+use std::net::TcpStream;
+
+use crate::dispatch::command_dispatch::CommandDispatch;
+
+use crate::server::Server;
+use crate::message::Respond;
+
+pub struct Userhost {
+    pub nicknames: Vec<String>,
+}
+
+pub struct Ison {
+    pub nicknames: Vec<String>,
+}
+
+impl Userhost {
+
+    /// The maximum number of nicknames per USERHOST command.
+    pub const MAX_NICKNAMES: usize = 5;
+}
+
+impl CommandDispatch for Userhost {
+    fn dispatch(&self, server: &mut Server, client: &mut TcpStream, client_id: usize) {
+
+        // Get the nickname of the current user
+        let nick = server.users.find(client_id).unwrap().nickname();
+        let host = server.config.get_host();
+
+        // At least one nickname is required
+        if self.nicknames.is_empty() {
+            send!(client; Respond::to(host, &nick).err_need_more_params("USERHOST"));
+            return;
+        }
+
+        // Build a reply like `nick*=+user@host` for every known user.
+        // The `*` marks operators, `+` and `-` tell whether the user is here or away.
+        let replies: Vec<String> = self.nicknames.iter()
+            .take(Userhost::MAX_NICKNAMES)
+            .filter_map(|nickname| server.users.find_by_name(nickname))
+            .filter(|user| user.is_registered())
+            .map(|user| format!(
+                "{}{}={}{}@{}",
+                user.nickname(),
+                if user.modes().operator { "*" } else { "" },
+                if user.away().is_some() { "-" } else { "+" },
+                user.username(),
+                user.hostname(),
+            ))
+            .collect();
+
+        // Tell the client about the users
+        send!(client; Respond::to(host, &nick).userhost(&replies.join(" ")));
+    }
+}
+
+impl CommandDispatch for Ison {
+    fn dispatch(&self, server: &mut Server, client: &mut TcpStream, client_id: usize) {
+
+        // Get the nickname of the current user
+        let nick = server.users.find(client_id).unwrap().nickname();
+        let host = server.config.get_host();
+
+        // At least one nickname is required
+        if self.nicknames.is_empty() {
+            send!(client; Respond::to(host, &nick).err_need_more_params("ISON"));
+            return;
+        }
+
+        // Collect the nicknames of all users that are online, as they are known to the server
+        let online: Vec<String> = self.nicknames.iter()
+            .filter_map(|nickname| server.users.find_by_name(nickname))
+            .filter(|user| user.is_registered())
+            .map(|user| user.nickname())
+            .collect();
+
+        // Tell the client about the users
+        send!(client; Respond::to(host, &nick).ison(&online.join(" ")));
+    }
+}
\ No newline at end of file
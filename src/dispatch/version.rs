@@ -0,0 +1,42 @@
+use std::net::TcpStream;
+
+use crate::dispatch::command_dispatch::CommandDispatch;
+
+use crate::server::Server;
+use crate::message::Respond;
+
+pub struct Version {}
+
+impl Version {
+
+    /// Tells the client about the ISUPPORT tokens of the server.
+    /// 
+    /// The tokens are split across as many RPL_ISUPPORT lines
+    /// as needed to stay within the maximum message length.
+    pub fn send_isupport(server: &Server, client: &mut TcpStream, nick: &str) {
+
+        // Calculate the space available for tokens in a single line.
+        // The line looks like ":host 005 nick tokens :are supported by this server\r\n"
+        let host = server.config.get_host();
+        let overhead = host.len() + nick.len() + 40;
+        let max_len = 512 - overhead;
+
+        for tokens in server.isupport.lines(max_len) {
+            send!(client; Respond::to(host, nick).isupport(&tokens));
+        }
+    }
+}
+
+impl CommandDispatch for Version {
+    fn dispatch(&self, server: &mut Server, client: &mut TcpStream, client_id: usize) {
+
+        // Get the nickname of the current user
+        let nick = server.users.find(client_id).unwrap().nickname();
+
+        // Tell the client about the server version
+        send!(client; Respond::to(server.config.get_host(), &nick).version(&format!("zircond-{}", crate_version!()), "Zircon IRCd"));
+
+        // Tell the client about the server capabilities
+        Version::send_isupport(server, client, &nick);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,123 @@
+use std::net::TcpStream;
+
+use crate::dispatch::command_dispatch::CommandDispatch;
+
+use crate::server::{Server, User};
+use crate::message::Respond;
+
+pub struct Who {
+    pub mask: String,
+    pub options: Option<String>,
+}
+
+impl Who {
+
+    /// Field letters supported by WHOX, in the order they are sent.
+    const WHOX_FIELDS: &'static str = "tcuihsnfdlaor";
+
+    /// Extracts the WHOX fields and query token from the options.
+    /// 
+    /// `%tcuhnfar,42` yields the fields `tcuhnfar` and the token `42`.
+    fn whox_options(&self) -> Option<(String, Option<String>)> {
+        let options = self.options.as_ref()?;
+        let whox = &options[options.find('%')? + 1..];
+        let mut parts = whox.splitn(2, ',');
+        let fields = parts.next().unwrap_or("").to_owned();
+        let token = parts.next().map(|token| token.to_owned());
+        Some((fields, token))
+    }
+
+    /// Builds the flags of a user, e.g. `H*@` for a server and channel operator that is here,
+    /// or `G` for a user that is away.
+    /// 
+    /// - `prefix` - The channel status prefix of the user
+    fn flags(user: &User, prefix: &str) -> String {
+        let presence = if user.away().is_some() { "G" } else { "H" };
+        let operator = if user.modes().operator { "*" } else { "" };
+        format!("{}{}{}", presence, operator, prefix)
+    }
+
+    /// Builds the requested WHOX fields for a single user.
+    fn whox_fields(server: &Server, user: &User, channel_name: &str, prefix: &str, fields: &str, token: &Option<String>) -> Vec<String> {
+        Who::WHOX_FIELDS.chars()
+            .filter(|field| fields.contains(*field))
+            .map(|field| match field {
+                't' => token.clone().unwrap_or_else(|| "0".to_owned()),
+                'c' => channel_name.to_owned(),
+                'u' => user.username(),
+                'i' => user.hostname().to_owned(),
+                'h' => user.hostname().to_owned(),
+                's' => server.config.get_host().to_owned(),
+                'n' => user.nickname(),
+                'f' => Who::flags(user, prefix),
+                'd' => "0".to_owned(),
+                'l' => "0".to_owned(),
+                'a' => user.account().unwrap_or("0").to_owned(),
+                'o' => "n/a".to_owned(),
+                'r' => user.realname(),
+                _ => unreachable!(),
+            })
+            .collect()
+    }
+}
+
+impl CommandDispatch for Who {
+    fn dispatch(&self, server: &mut Server, client: &mut TcpStream, client_id: usize) {
+
+        // Get the nickname of the current user
+        let nick = server.users.find(client_id).unwrap().nickname();
+
+        // Collect all matching users along with the channel and status prefix to report
+        let mut matches: Vec<(String, usize, String)> = Vec::new();
+        if self.mask.starts_with('#') {
+
+            // List all visible users in the channel, like NAMES does
+            if let Some(channel) = server.channels.get(&self.mask).filter(|channel| super::Names::is_channel_visible(channel, client_id)) {
+                for (user, prefix) in super::Names::visible_members(server, channel, client_id) {
+                    matches.push((channel.name.clone(), user.id, prefix));
+                }
+            }
+        } else {
+
+            // List all users whose nickname matches the mask
+            // Invisible users are only listed for users sharing a channel
+            let is_visible = |user: &&User| !user.modes().invisible || user.id == client_id || server.channels.shares_channel(user.id, client_id);
+            for user in server.users.iter().filter(|user| user.is_registered()).filter(is_visible) {
+                if self.mask == "0" || crate::mask::matches(&self.mask, &user.nickname(), server.casemapping) {
+                    matches.push(("*".to_owned(), user.id, String::new()));
+                }
+            }
+        }
+
+        // Tell the client about the users
+        let whox = self.whox_options();
+        let host = server.config.get_host();
+        for (channel_name, other_client_id, prefix) in matches {
+
+            // Find the user
+            let user = match server.users.find(other_client_id) {
+                Some(user) => user,
+                None => continue,
+            };
+
+            match &whox {
+
+                // Send the requested WHOX fields
+                Some((fields, token)) => {
+                    let fields = Who::whox_fields(server, user, &channel_name, &prefix, fields, token);
+                    send!(client; Respond::to(host, &nick).whox_reply(fields));
+                }
+
+                // Send a regular WHO reply
+                None => {
+                    let (username, nickname, flags) = (user.username(), user.nickname(), Who::flags(user, &prefix));
+                    let fields = [channel_name.as_ref(), username.as_ref(), user.hostname(), host, nickname.as_ref(), flags.as_ref()];
+                    send!(client; Respond::to(host, &nick).who_reply(&fields, 0, &user.realname()));
+                }
+            }
+        }
+
+        // Mark the end of the user list
+        send!(client; Respond::to(host, &nick).who_end(&self.mask));
+    }
+}
\ No newline at end of file
@@ -0,0 +1,74 @@
+use std::net::TcpStream;
+
+use crate::dispatch::command_dispatch::CommandDispatch;
+
+use crate::server::Server;
+use crate::message::Respond;
+
+pub struct Whois {
+    pub nicknames: Vec<String>,
+}
+
+impl CommandDispatch for Whois {
+    fn dispatch(&self, server: &mut Server, client: &mut TcpStream, client_id: usize) {
+
+        // Get the nickname of the current user
+        let nick = server.users.find(client_id).unwrap().nickname();
+        let host = server.config.get_host();
+
+        for nickname in &self.nicknames {
+
+            // Find the user
+            let user = match server.users.find_by_name(nickname) {
+                Some(user) => user,
+                None => {
+
+                    // Notify the user about the error
+                    send!(client; Respond::to(host, &nick).err_no_such_nick(nickname));
+                    send!(client; Respond::to(host, &nick).whois_end(nickname));
+                    continue;
+                }
+            };
+            let nickname = user.nickname();
+
+            // Tell the client about the user
+            send!(client; Respond::to(host, &nick).whois_user(&nickname, &user.username(), user.hostname(), &user.realname()));
+
+            // Tell the client about the channels of the user
+            // Hidden channels are only shown to users in the same channel
+            let status_modes = server.config.get_status_modes();
+            let channels = server.channels.iter()
+                .filter(|channel| channel.contains(user.id))
+                .filter(|channel| !channel.modes.is_hidden() || channel.contains(client_id))
+                .map(|channel| format!("{}{}", channel.prefix(user.id, &status_modes), channel.name))
+                .collect::<Vec<_>>();
+            if !channels.is_empty() {
+                send!(client; Respond::to(host, &nick).whois_channels(&nickname, &channels.join(" ")));
+            }
+
+            // Tell the client about the server of the user
+            send!(client; Respond::to(host, &nick).whois_server(&nickname, host, "Zircon IRCd"));
+
+            // Tell the client whether the user is a server operator
+            if user.modes().operator {
+                send!(client; Respond::to(host, &nick).whois_operator(&nickname));
+            }
+
+            // Tell the client about the account of the user
+            if let Some(account) = user.account() {
+                send!(client; Respond::to(host, &nick).whois_account(&nickname, account));
+            }
+
+            // Tell the client whether the user is away
+            if let Some(away) = user.away() {
+                send!(client; Respond::to(host, &nick).away_reply(&nickname, away));
+            }
+
+            // Tell the client about the idle and signon time of the user
+            send!(client; Respond::to(host, &nick).whois_idle(&nickname, user.idle_time(), user.signon_time()));
+
+            // Mark the end of the user information
+            send!(client; Respond::to(host, &nick).whois_end(&nickname));
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,42 @@
+use std::net::TcpStream;
+
+use crate::dispatch::command_dispatch::CommandDispatch;
+
+use crate::server::Server;
+use crate::message::Respond;
+
+pub struct Whowas {
+    pub nickname: String,
+    pub count: Option<usize>,
+}
+
+impl CommandDispatch for Whowas {
+    fn dispatch(&self, server: &mut Server, client: &mut TcpStream, client_id: usize) {
+
+        // Get the nickname of the current user
+        let nick = server.users.find(client_id).unwrap().nickname();
+        let host = server.config.get_host();
+
+        // A count of zero or less means all entries
+        let count = match self.count {
+            Some(count) if count > 0 => count,
+            _ => usize::MAX,
+        };
+
+        // Tell the client about the previous users of the nickname
+        let mut found = false;
+        for entry in server.whowas.find_by_name(&self.nickname).take(count) {
+            send!(client; Respond::to(host, &nick).whowas_user(&entry.nickname, &entry.username, &entry.hostname, &entry.realname));
+            send!(client; Respond::to(host, &nick).whois_server(&entry.nickname, host, &format!("Left at {} (unix time)", entry.time)));
+            found = true;
+        }
+
+        // Notify the user if the nickname is unknown
+        if !found {
+            send!(client; Respond::to(host, &nick).err_was_no_such_nick(&self.nickname));
+        }
+
+        // Mark the end of the history
+        send!(client; Respond::to(host, &nick).whowas_end(&self.nickname));
+    }
+}
\ No newline at end of file
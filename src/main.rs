@@ -12,6 +12,10 @@ macro_rules! crate_version {
 }
 
 mod protocol;
+mod time;
+mod casemapping;
+mod mask;
+mod name;
 mod message;
 mod parser;
 mod config;
@@ -26,17 +30,20 @@ use std::io::{Read, Write};
 
 fn main() -> std::io::Result<()> {
 
+    // The path of the configuration file may be passed as the first argument
+    let config_path = std::env::args().nth(1).unwrap_or_else(|| "config.toml".to_owned());
+
     // Read or create configuration
     let config = {
-        if std::path::Path::new("config.toml").exists() {
-            read_config()
+        if std::path::Path::new(&config_path).exists() {
+            read_config(&config_path)
         } else {
-            create_config()
+            create_config(&config_path)
         }
     }?;
 
     // Create server
-    let mut server = Server::new(config);
+    let mut server = Server::new(config, config_path);
 
     // Listen
     server.listen();
@@ -45,7 +52,7 @@ fn main() -> std::io::Result<()> {
     Ok(())
 }
 
-fn create_config() -> std::io::Result<ServerConfig> {
+fn create_config(path: &str) -> std::io::Result<ServerConfig> {
 
     // Create default configuration
     let config = ServerConfig::default();
@@ -54,7 +61,7 @@ fn create_config() -> std::io::Result<ServerConfig> {
     if let Ok(config_text) = toml::to_string(&config) {
         
         // Write to file
-        let mut file = File::create("config.toml")?;
+        let mut file = File::create(path)?;
         file.write_all(config_text.as_ref())?;
 
         // Return the configuration
@@ -64,10 +71,10 @@ fn create_config() -> std::io::Result<ServerConfig> {
     }
 }
 
-fn read_config() -> std::io::Result<ServerConfig> {
+fn read_config(path: &str) -> std::io::Result<ServerConfig> {
 
     // Open config file
-    let mut config_file = File::open("config.toml")?;
+    let mut config_file = File::open(path)?;
 
     // Read file into buffer
     let mut config_text = String::new();
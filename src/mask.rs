@@ -0,0 +1,155 @@
+use crate::casemapping::Casemapping;
+
+/// Completes a mask to the full `nick!user@host` form.
+/// 
+/// - `alice` becomes `alice!*@*`
+/// - `*@example.com` becomes `*!*@example.com`
+/// - `alice!bob` becomes `alice!bob@*`
+pub fn normalize(mask: &str) -> String {
+    match (mask.contains('!'), mask.contains('@')) {
+        (true, true) => mask.to_owned(),
+        (false, true) => format!("*!{}", mask),
+        (true, false) => format!("{}@*", mask),
+        (false, false) => format!("{}!*@*", mask),
+    }
+}
+
+/// A single token of a glob mask.
+#[derive(Clone, Copy, PartialEq)]
+enum Token {
+    /// `*` - Any number of characters
+    Star,
+
+    /// `?` - Exactly one character
+    Any,
+
+    /// A character that has to match, including escaped `*`, `?` and `\`
+    Literal(char),
+}
+
+/// Splits a mask into tokens, folding literal characters under the casemapping.
+/// 
+/// The escapes are resolved before folding, since `\` is a letter in rfc1459.
+fn tokenize(mask: &str, casemapping: Casemapping) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    let mut chars = mask.chars();
+    while let Some(chr) = chars.next() {
+        tokens.push(match chr {
+            '*' => Token::Star,
+            '?' => Token::Any,
+
+            // A trailing backslash matches itself
+            '\\' => Token::Literal(casemapping.fold_char(chars.next().unwrap_or('\\'))),
+            chr => Token::Literal(casemapping.fold_char(chr)),
+        });
+    }
+    tokens
+}
+
+/// Tests whether a text matches a glob mask.
+/// 
+/// `*` matches any number of characters, `?` matches exactly one character.
+/// A backslash escapes the following character, so `\*` only matches a literal `*`.
+/// Characters are compared under the given casemapping.
+pub fn matches(mask: &str, text: &str, casemapping: Casemapping) -> bool {
+    let mask = tokenize(mask, casemapping);
+    let text: Vec<char> = casemapping.fold(text).chars().collect();
+
+    let (mut m, mut t) = (0, 0);
+    let mut backtrack: Option<(usize, usize)> = None;
+    while t < text.len() {
+        match mask.get(m) {
+
+            // Remember the star, initially matching nothing
+            Some(Token::Star) => {
+                backtrack = Some((m, t));
+                m += 1;
+            }
+
+            // Consume a matching character
+            Some(Token::Any) => {
+                m += 1;
+                t += 1;
+            }
+            Some(Token::Literal(chr)) if *chr == text[t] => {
+                m += 1;
+                t += 1;
+            }
+
+            // Let the last star consume one more character
+            _ => match backtrack {
+                Some((star_m, star_t)) => {
+                    backtrack = Some((star_m, star_t + 1));
+                    m = star_m + 1;
+                    t = star_t + 1;
+                }
+                None => return false,
+            },
+        }
+    }
+
+    // Trailing stars match the empty remainder
+    mask[m..].iter().all(|token| *token == Token::Star)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wildcards() {
+        let cases = [
+            ("*", "", true),
+            ("*", "alice!a@host", true),
+            ("a*", "alice", true),
+            ("a*", "bob", false),
+            ("*e", "alice", true),
+            ("a*c*e", "alice", true),
+            ("a*c*e", "alicf", false),
+            ("a?ice", "alice", true),
+            ("a?ice", "aice", false),
+            ("???", "bob", true),
+            ("???", "bo", false),
+            ("*!*@*.example.com", "alice!a@irc.example.com", true),
+            ("*!*@*.example.com", "alice!a@example.com", false),
+            ("**a", "aaa", true),
+        ];
+        for (mask, text, expected) in cases.iter() {
+            assert_eq!(matches(mask, text, Casemapping::Ascii), *expected, "{} ~ {}", mask, text);
+        }
+    }
+
+    #[test]
+    fn test_escapes() {
+        let cases = [
+            ("a\\*", "a*", true),
+            ("a\\*", "ab", false),
+            ("a\\?", "a?", true),
+            ("a\\?", "ab", false),
+            ("a\\\\b", "a\\b", true),
+            ("a\\", "a\\", true),
+            ("\\**", "*anything", true),
+        ];
+        for (mask, text, expected) in cases.iter() {
+            assert_eq!(matches(mask, text, Casemapping::Ascii), *expected, "{} ~ {}", mask, text);
+        }
+    }
+
+    #[test]
+    fn test_casemapping() {
+        assert!(matches("ALICE!*@*", "alice!a@host", Casemapping::Ascii));
+        assert!(matches("[bot]*", "{BOT}x", Casemapping::Rfc1459));
+        assert!(matches("a^*", "A~b", Casemapping::Rfc1459));
+        assert!(!matches("a^*", "A~b", Casemapping::Rfc1459Strict));
+        assert!(matches("a\\|b", "a\\b", Casemapping::Rfc1459Strict));
+        assert!(!matches("[bot]*", "{bot}x", Casemapping::Ascii));
+    }
+
+    #[test]
+    fn test_normalize() {
+        assert_eq!(normalize("alice"), "alice!*@*");
+        assert_eq!(normalize("*@example.com"), "*!*@example.com");
+        assert_eq!(normalize("alice!bob"), "alice!bob@*");
+        assert_eq!(normalize("a!b@c"), "a!b@c");
+    }
+}
\ No newline at end of file
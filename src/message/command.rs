@@ -1,4 +1,4 @@
-#[derive(Debug)]
+#[derive(Debug, PartialEq)]
 pub enum IrcMessageCommand {
     None,
 
@@ -6,6 +6,11 @@ pub enum IrcMessageCommand {
     // Authentication
     //
 
+    /// PASS - Set the connection password
+    /// 
+    /// * `0` - Password
+    Pass(Option<String>),
+
     /// NICK - Set nickname
     /// 
     /// * `0` - Nickname
@@ -17,6 +22,12 @@ pub enum IrcMessageCommand {
     /// * `1` - Realname
     User(String, Option<String>),
 
+    /// CAP - IRCv3 capability negotiation
+    /// 
+    /// * `0` - Subcommand
+    /// * `1` - Capabilities
+    Cap(String, Option<String>),
+
     //
     // Channels
     //
@@ -33,10 +44,120 @@ pub enum IrcMessageCommand {
     /// * `1` - Reason
     Part(Vec<String>, Option<String>),
 
-    /// WHO - List users in a specific channel
+    /// TOPIC - Query or change the topic of a channel
     /// 
     /// * `0` - Channel name
-    Who(String),
+    /// * `1` - New topic
+    Topic(String, Option<String>),
+
+    /// INVITE - Invite a user to a channel, or list pending invites
+    /// 
+    /// * `0` - Nickname and channel name
+    Invite(Option<(String, String)>),
+
+    /// KICK - Remove users from a channel
+    /// 
+    /// * `0` - Channel name
+    /// * `1` - Nickname(s)
+    /// * `2` - Reason
+    Kick(String, Vec<String>, Option<String>),
+
+    /// MODE - Query or change the modes of a channel or user
+    /// 
+    /// * `0` - Target
+    /// * `1` - Mode string
+    /// * `2` - Mode arguments
+    Mode(String, Option<String>, Vec<String>),
+
+    /// NAMES - List nicknames in channels
+    /// 
+    /// * `0` - Channel name(s)
+    Names(Option<Vec<String>>),
+
+    /// LIST - List channels
+    /// 
+    /// * `0` - Channel names, masks and ELIST filters
+    List(Option<Vec<String>>),
+
+    /// WHO - List users matching a channel name or nickname
+    /// 
+    /// * `0` - Mask
+    /// * `1` - WHOX fields and query token
+    Who(String, Option<String>),
+
+    //
+    // Users
+    //
+
+    /// WHOIS - Query information about users
+    /// 
+    /// * `0` - Nickname(s)
+    Whois(Vec<String>),
+
+    /// USERHOST - Query the hostnames of users
+    /// 
+    /// * `0` - Nicknames
+    Userhost(Vec<String>),
+
+    /// ISON - Query whether users are online
+    /// 
+    /// * `0` - Nicknames
+    Ison(Vec<String>),
+
+    /// WHOWAS - Query information about nicknames that are no longer in use
+    /// 
+    /// * `0` - Nickname
+    /// * `1` - Maximum number of entries
+    Whowas(String, Option<usize>),
+
+    /// AWAY - Set or clear the away message
+    /// 
+    /// * `0` - Away message
+    Away(Option<String>),
+
+    //
+    // Server
+    //
+
+    /// VERSION - Query the server version
+    Version,
+
+    /// MOTD - Query the message of the day
+    Motd,
+
+    /// LUSERS - Query the number of users and channels
+    Lusers,
+
+    /// TIME - Query the local time of the server
+    Time,
+
+    /// INFO - Query information about the server software
+    Info,
+
+    /// ADMIN - Query administrative information about the server
+    Admin,
+
+    //
+    // Operators
+    //
+
+    /// OPER - Obtain server operator privileges
+    /// 
+    /// * `0` - Operator name
+    /// * `1` - Password
+    Oper(String, String),
+
+    /// KILL - Disconnect a user from the server
+    /// 
+    /// * `0` - Nickname
+    /// * `1` - Reason
+    Kill(String, Option<String>),
+
+    /// REHASH - Reload the server configuration
+    Rehash,
+
+    /// DIE - Shut down the server
+    Die,
 
     //
     // Messaging
@@ -48,6 +169,12 @@ pub enum IrcMessageCommand {
     /// * `1` - Message
     Privmsg(String, String),
 
+    /// NOTICE - Notice, which must never be answered automatically
+    /// 
+    /// * `0` - Target
+    /// * `1` - Message
+    Notice(String, String),
+
     //
     // Misc
     //
@@ -56,4 +183,12 @@ pub enum IrcMessageCommand {
     /// 
     /// * `0` - Ping id
     Ping(String),
+
+    /// PONG - Reply to a server PING
+    Pong,
+
+    /// QUIT - Terminate the connection
+    /// 
+    /// * `0` - Quit message
+    Quit(Option<String>),
 }
\ No newline at end of file
@@ -1,5 +1,5 @@
 use crate::message::{IrcMessageTags, IrcMessagePrefix, IrcMessageCommand};
-use crate::parser::IrcMessageParser;
+use crate::parser::{IrcMessageParser, ParseError};
 
 pub struct IrcMessageRequest {
     tags: Option<IrcMessageTags>,
@@ -16,7 +16,7 @@ impl IrcMessageRequest {
         }
     }
 
-    pub fn parse(line: &str) -> Self {
+    pub fn parse(line: &str) -> Result<Self, ParseError> {
         IrcMessageParser::parse(line)
     }
 }
@@ -1,4 +1,5 @@
 use crate::protocol::*;
+use crate::server::ListEntry;
 
 pub enum CommandType {
     None,
@@ -54,6 +55,38 @@ impl<'a> ResponseBuilder<'a> {
         self
     }
 
+    pub fn rpl_created(mut self, time: &str) -> Self {
+        self.command = CommandType::Code(RPL_CREATED);
+        self.parameters.push(format!("This server was created {}", time));
+        self
+    }
+
+    pub fn my_info(mut self, version: &str, user_modes: &str, channel_modes: &str, parameter_modes: &str) -> Self {
+        self.auto_insert_trailing_separator = false;
+        self.command = CommandType::Code(RPL_MYINFO);
+        self.parameters.push(self.source.to_owned());
+        self.parameters.push(version.to_owned());
+        self.parameters.push(user_modes.to_owned());
+        self.parameters.push(channel_modes.to_owned());
+        self.parameters.push(parameter_modes.to_owned());
+        self
+    }
+
+    pub fn isupport(mut self, tokens: &[String]) -> Self {
+        self.command = CommandType::Code(RPL_ISUPPORT__);
+        self.parameters.extend(tokens.iter().cloned());
+        self.parameters.push("are supported by this server".to_owned());
+        self
+    }
+
+    pub fn version(mut self, version: &str, comments: &str) -> Self {
+        self.command = CommandType::Code(RPL_VERSION);
+        self.parameters.push(version.to_owned());
+        self.parameters.push(self.source.to_owned());
+        self.parameters.push(comments.to_owned());
+        self
+    }
+
     pub fn motd_start(mut self) -> Self {
         self.auto_insert_trailing_separator = false;
         self.command = CommandType::Code(RPL_MOTDSTART);
@@ -74,15 +107,151 @@ impl<'a> ResponseBuilder<'a> {
         self
     }
 
+    //
+    // Topics
+    //
+
+    pub fn topic_reply(mut self, channel: &str, topic: &str) -> Self {
+        self.command = CommandType::Code(RPL_TOPIC);
+        self.parameters.push(channel.to_owned());
+        self.parameters.push(topic.to_owned());
+        self
+    }
+
+    pub fn no_topic(mut self, channel: &str) -> Self {
+        self.command = CommandType::Code(RPL_NOTOPIC);
+        self.parameters.push(channel.to_owned());
+        self.parameters.push("No topic is set.".to_owned());
+        self
+    }
+
+    pub fn topic_who_time(mut self, channel: &str, setter: &str, time: u64) -> Self {
+        self.auto_insert_trailing_separator = false;
+        self.command = CommandType::Code(RPL_TOPICWHOTIME__IRCU);
+        self.parameters.push(channel.to_owned());
+        self.parameters.push(setter.to_owned());
+        self.parameters.push(time.to_string());
+        self
+    }
+
+    //
+    // Modes
+    //
+
+    pub fn channel_mode_is(mut self, channel: &str, modes: &str, arguments: &[String]) -> Self {
+        self.auto_insert_trailing_separator = false;
+        self.command = CommandType::Code(RPL_CHANNELMODEIS);
+        self.parameters.push(channel.to_owned());
+        self.parameters.push(modes.to_owned());
+        self.parameters.extend(arguments.iter().cloned());
+        self
+    }
+
+    pub fn creation_time(mut self, channel: &str, time: u64) -> Self {
+        self.auto_insert_trailing_separator = false;
+        self.command = CommandType::Code(RPL_CREATIONTIME__BAHAMUT);
+        self.parameters.push(channel.to_owned());
+        self.parameters.push(time.to_string());
+        self
+    }
+
+    pub fn user_mode_is(mut self, modes: &str) -> Self {
+        self.auto_insert_trailing_separator = false;
+        self.command = CommandType::Code(RPL_UMODEIS);
+        self.parameters.push(modes.to_owned());
+        self
+    }
+
+    pub fn ban_list(mut self, channel: &str, entry: &ListEntry) -> Self {
+        self.auto_insert_trailing_separator = false;
+        self.command = CommandType::Code(RPL_BANLIST);
+        self.parameters.push(channel.to_owned());
+        self.parameters.push(entry.mask.clone());
+        self.parameters.push(entry.setter.clone());
+        self.parameters.push(entry.time.to_string());
+        self
+    }
+
+    pub fn ban_list_end(mut self, channel: &str) -> Self {
+        self.command = CommandType::Code(RPL_ENDOFBANLIST);
+        self.parameters.push(channel.to_owned());
+        self.parameters.push("End of channel ban list.".to_owned());
+        self
+    }
+
+    pub fn exception_list(mut self, channel: &str, entry: &ListEntry) -> Self {
+        self.auto_insert_trailing_separator = false;
+        self.command = CommandType::Code(RPL_EXCEPTLIST);
+        self.parameters.push(channel.to_owned());
+        self.parameters.push(entry.mask.clone());
+        self.parameters.push(entry.setter.clone());
+        self.parameters.push(entry.time.to_string());
+        self
+    }
+
+    pub fn exception_list_end(mut self, channel: &str) -> Self {
+        self.command = CommandType::Code(RPL_ENDOFEXCEPTLIST);
+        self.parameters.push(channel.to_owned());
+        self.parameters.push("End of channel exception list.".to_owned());
+        self
+    }
+
+    pub fn invite_exception_list(mut self, channel: &str, entry: &ListEntry) -> Self {
+        self.auto_insert_trailing_separator = false;
+        self.command = CommandType::Code(RPL_INVITELIST);
+        self.parameters.push(channel.to_owned());
+        self.parameters.push(entry.mask.clone());
+        self.parameters.push(entry.setter.clone());
+        self.parameters.push(entry.time.to_string());
+        self
+    }
+
+    pub fn invite_exception_list_end(mut self, channel: &str) -> Self {
+        self.command = CommandType::Code(RPL_ENDOFINVITELIST);
+        self.parameters.push(channel.to_owned());
+        self.parameters.push("End of channel invite exception list.".to_owned());
+        self
+    }
+
+    pub fn inviting(mut self, nick: &str, channel: &str) -> Self {
+        self.auto_insert_trailing_separator = false;
+        self.command = CommandType::Code(RPL_INVITING);
+        self.parameters.push(nick.to_owned());
+        self.parameters.push(channel.to_owned());
+        self
+    }
+
+    pub fn invite_list(mut self, channel: &str) -> Self {
+        self.auto_insert_trailing_separator = false;
+        self.command = CommandType::Code(RPL_INVITELIST__HYBRID);
+        self.parameters.push(channel.to_owned());
+        self
+    }
+
+    pub fn invite_list_end(mut self) -> Self {
+        self.command = CommandType::Code(RPL_ENDOFINVITELIST__HYBRID);
+        self.parameters.push("End of /INVITE list.".to_owned());
+        self
+    }
+
+    pub fn mode(mut self, modes: &str, arguments: &[String]) -> Self {
+        self.auto_insert_trailing_separator = false;
+        self.command = CommandType::Name("MODE");
+        self.parameters.push(self.target.to_owned());
+        self.parameters.push(modes.to_owned());
+        self.parameters.extend(arguments.iter().cloned());
+        self
+    }
+
     //
     // Listings
     //
 
-    pub fn names_reply(mut self, channel_mode: &str, channel: &str, user_mode: &str, nickname: &str) -> Self {
+    pub fn names_reply(mut self, channel_mode: &str, channel: &str, nicknames: &str) -> Self {
         self.command = CommandType::Code(RPL_NAMREPLY);
-        self.parameters.push(channel.to_owned());
         self.parameters.push(channel_mode.to_owned());
-        self.parameters.push(format!("{}{}", user_mode, nickname));
+        self.parameters.push(channel.to_owned());
+        self.parameters.push(nicknames.to_owned());
         self
     }
 
@@ -93,6 +262,290 @@ impl<'a> ResponseBuilder<'a> {
         self
     }
 
+    pub fn list_start(mut self) -> Self {
+        self.command = CommandType::Code(RPL_LISTSTART);
+        self.parameters.push("Channel".to_owned());
+        self.parameters.push("Users  Name".to_owned());
+        self
+    }
+
+    pub fn list(mut self, channel: &str, users: usize, topic: &str) -> Self {
+        self.command = CommandType::Code(RPL_LIST);
+        self.parameters.push(channel.to_owned());
+        self.parameters.push(users.to_string());
+        self.parameters.push(topic.to_owned());
+        self
+    }
+
+    pub fn list_end(mut self) -> Self {
+        self.command = CommandType::Code(RPL_LISTEND);
+        self.parameters.push("End of /LIST".to_owned());
+        self
+    }
+
+    /// Builds a RPL_WHOREPLY line.
+    /// 
+    /// - `fields` - Channel, username, host, server, nickname and flags
+    /// - `hopcount` - The hopcount of the user
+    /// - `realname` - The realname of the user
+    pub fn who_reply(mut self, fields: &[&str], hopcount: u32, realname: &str) -> Self {
+        self.command = CommandType::Code(RPL_WHOREPLY);
+        self.parameters.extend(fields.iter().map(|field| field.to_string()));
+        self.parameters.push(format!("{} {}", hopcount, realname));
+        self
+    }
+
+    /// Builds a WHOX reply line (RPL_WHOSPCRPL).
+    /// 
+    /// - `fields` - The requested fields, in protocol order
+    pub fn whox_reply(mut self, fields: Vec<String>) -> Self {
+        self.command = CommandType::Code(RPL_WHOSPCRPL__IRCU);
+        self.parameters = fields;
+        self
+    }
+
+    pub fn who_end(mut self, mask: &str) -> Self {
+        self.command = CommandType::Code(RPL_ENDOFWHO);
+        self.parameters.push(mask.to_owned());
+        self.parameters.push("End of /WHO list.".to_owned());
+        self
+    }
+
+    //
+    // User queries
+    //
+
+    pub fn whois_user(mut self, nick: &str, username: &str, host: &str, realname: &str) -> Self {
+        self.command = CommandType::Code(RPL_WHOISUSER);
+        self.parameters.push(nick.to_owned());
+        self.parameters.push(username.to_owned());
+        self.parameters.push(host.to_owned());
+        self.parameters.push("*".to_owned());
+        self.parameters.push(realname.to_owned());
+        self
+    }
+
+    pub fn whois_server(mut self, nick: &str, server: &str, info: &str) -> Self {
+        self.command = CommandType::Code(RPL_WHOISSERVER);
+        self.parameters.push(nick.to_owned());
+        self.parameters.push(server.to_owned());
+        self.parameters.push(info.to_owned());
+        self
+    }
+
+    pub fn whois_account(mut self, nick: &str, account: &str) -> Self {
+        self.command = CommandType::Code(RPL_WHOISACCOUNT__IRCU);
+        self.parameters.push(nick.to_owned());
+        self.parameters.push(account.to_owned());
+        self.parameters.push("is logged in as".to_owned());
+        self
+    }
+
+    pub fn whois_operator(mut self, nick: &str) -> Self {
+        self.command = CommandType::Code(RPL_WHOISOPERATOR);
+        self.parameters.push(nick.to_owned());
+        self.parameters.push("is an IRC operator.".to_owned());
+        self
+    }
+
+    pub fn whois_channels(mut self, nick: &str, channels: &str) -> Self {
+        self.command = CommandType::Code(RPL_WHOISCHANNELS);
+        self.parameters.push(nick.to_owned());
+        self.parameters.push(channels.to_owned());
+        self
+    }
+
+    pub fn whois_idle(mut self, nick: &str, idle_time: u64, signon_time: u64) -> Self {
+        self.command = CommandType::Code(RPL_WHOISIDLE);
+        self.parameters.push(nick.to_owned());
+        self.parameters.push(idle_time.to_string());
+        self.parameters.push(signon_time.to_string());
+        self.parameters.push("seconds idle, signon time".to_owned());
+        self
+    }
+
+    pub fn away_reply(mut self, nick: &str, message: &str) -> Self {
+        self.command = CommandType::Code(RPL_AWAY);
+        self.parameters.push(nick.to_owned());
+        self.parameters.push(message.to_owned());
+        self
+    }
+
+    /// Builds a RPL_USERHOST reply.
+    /// 
+    /// - `replies` - Entries like `nick*=+user@host`, separated by spaces
+    pub fn userhost(mut self, replies: &str) -> Self {
+        self.command = CommandType::Code(RPL_USERHOST);
+        self.parameters.push(replies.to_owned());
+        self
+    }
+
+    /// Builds a RPL_ISON reply.
+    /// 
+    /// - `nicks` - The nicknames of the online users, separated by spaces
+    pub fn ison(mut self, nicks: &str) -> Self {
+        self.command = CommandType::Code(RPL_ISON);
+        self.parameters.push(nicks.to_owned());
+        self
+    }
+
+    pub fn unaway(mut self) -> Self {
+        self.command = CommandType::Code(RPL_UNAWAY);
+        self.parameters.push("You are no longer marked as being away".to_owned());
+        self
+    }
+
+    pub fn now_away(mut self) -> Self {
+        self.command = CommandType::Code(RPL_NOWAWAY);
+        self.parameters.push("You have been marked as being away".to_owned());
+        self
+    }
+
+    pub fn whois_end(mut self, nick: &str) -> Self {
+        self.command = CommandType::Code(RPL_ENDOFWHOIS);
+        self.parameters.push(nick.to_owned());
+        self.parameters.push("End of /WHOIS list.".to_owned());
+        self
+    }
+
+    pub fn whowas_user(mut self, nick: &str, username: &str, host: &str, realname: &str) -> Self {
+        self.command = CommandType::Code(RPL_WHOWASUSER);
+        self.parameters.push(nick.to_owned());
+        self.parameters.push(username.to_owned());
+        self.parameters.push(host.to_owned());
+        self.parameters.push("*".to_owned());
+        self.parameters.push(realname.to_owned());
+        self
+    }
+
+    pub fn whowas_end(mut self, nick: &str) -> Self {
+        self.command = CommandType::Code(RPL_ENDOFWHOWAS);
+        self.parameters.push(nick.to_owned());
+        self.parameters.push("End of WHOWAS.".to_owned());
+        self
+    }
+
+    //
+    // Server queries
+    //
+
+    pub fn luser_client(mut self, users: usize, invisible: usize) -> Self {
+        self.command = CommandType::Code(RPL_LUSERCLIENT);
+        self.parameters.push(format!("There are {} users and {} invisible on 1 servers", users, invisible));
+        self
+    }
+
+    pub fn luser_op(mut self, operators: usize) -> Self {
+        self.command = CommandType::Code(RPL_LUSEROP);
+        self.parameters.push(operators.to_string());
+        self.parameters.push("operator(s) online".to_owned());
+        self
+    }
+
+    pub fn luser_unknown(mut self, connections: usize) -> Self {
+        self.command = CommandType::Code(RPL_LUSERUNKNOWN);
+        self.parameters.push(connections.to_string());
+        self.parameters.push("unknown connection(s)".to_owned());
+        self
+    }
+
+    pub fn luser_channels(mut self, channels: usize) -> Self {
+        self.command = CommandType::Code(RPL_LUSERCHANNELS);
+        self.parameters.push(channels.to_string());
+        self.parameters.push("channels formed".to_owned());
+        self
+    }
+
+    pub fn luser_me(mut self, clients: usize) -> Self {
+        self.command = CommandType::Code(RPL_LUSERME);
+        self.parameters.push(format!("I have {} clients and 0 servers", clients));
+        self
+    }
+
+    pub fn local_users(mut self, users: usize, max_users: usize) -> Self {
+        self.command = CommandType::Code(RPL_LOCALUSERS__AIRCD_HYBRID_BAHAMUT);
+        self.parameters.push(users.to_string());
+        self.parameters.push(max_users.to_string());
+        self.parameters.push(format!("Current local users {}, max {}", users, max_users));
+        self
+    }
+
+    pub fn global_users(mut self, users: usize, max_users: usize) -> Self {
+        self.command = CommandType::Code(RPL_GLOBALUSERS__AIRCD_HYBRID_BAHAMUT);
+        self.parameters.push(users.to_string());
+        self.parameters.push(max_users.to_string());
+        self.parameters.push(format!("Current global users {}, max {}", users, max_users));
+        self
+    }
+
+    pub fn time(mut self, time: &str) -> Self {
+        self.command = CommandType::Code(RPL_TIME);
+        self.parameters.push(self.source.to_owned());
+        self.parameters.push(time.to_owned());
+        self
+    }
+
+    pub fn info(mut self, message: &str) -> Self {
+        self.command = CommandType::Code(RPL_INFO);
+        self.parameters.push(message.to_owned());
+        self
+    }
+
+    pub fn info_end(mut self) -> Self {
+        self.command = CommandType::Code(RPL_ENDOFINFO);
+        self.parameters.push("End of INFO list".to_owned());
+        self
+    }
+
+    pub fn admin_me(mut self) -> Self {
+        self.command = CommandType::Code(RPL_ADMINME);
+        self.parameters.push(self.source.to_owned());
+        self.parameters.push("Administrative info".to_owned());
+        self
+    }
+
+    pub fn admin_location(mut self, location: &str) -> Self {
+        self.command = CommandType::Code(RPL_ADMINLOC1);
+        self.parameters.push(location.to_owned());
+        self
+    }
+
+    pub fn admin_organization(mut self, organization: &str) -> Self {
+        self.command = CommandType::Code(RPL_ADMINLOC2);
+        self.parameters.push(organization.to_owned());
+        self
+    }
+
+    pub fn admin_email(mut self, email: &str) -> Self {
+        self.command = CommandType::Code(RPL_ADMINEMAIL);
+        self.parameters.push(email.to_owned());
+        self
+    }
+
+    //
+    // Operators
+    //
+
+    pub fn youre_oper(mut self) -> Self {
+        self.command = CommandType::Code(RPL_YOUREOPER);
+        self.parameters.push("You are now an IRC operator".to_owned());
+        self
+    }
+
+    pub fn rehashing(mut self, file: &str) -> Self {
+        self.command = CommandType::Code(RPL_REHASHING);
+        self.parameters.push(file.to_owned());
+        self.parameters.push("Rehashing".to_owned());
+        self
+    }
+
+    pub fn kill(mut self, path: String) -> Self {
+        self.command = CommandType::Name("KILL");
+        self.parameters.push(self.target.to_owned());
+        self.parameters.push(path);
+        self
+    }
+
     //
     // Messaging
     //
@@ -104,6 +557,13 @@ impl<'a> ResponseBuilder<'a> {
         self
     }
 
+    pub fn notice(mut self, message: String) -> Self {
+        self.command = CommandType::Name("NOTICE");
+        self.parameters.push(self.target.to_owned());
+        self.parameters.push(message);
+        self
+    }
+
     //
     // Channels
     //
@@ -121,6 +581,21 @@ impl<'a> ResponseBuilder<'a> {
         self
     }
     
+    pub fn invite(mut self, channel: &str) -> Self {
+        self.command = CommandType::Name("INVITE");
+        self.parameters.push(self.target.to_owned());
+        self.parameters.push(channel.to_owned());
+        self
+    }
+
+    pub fn kick(mut self, nick: &str, reason: String) -> Self {
+        self.command = CommandType::Name("KICK");
+        self.parameters.push(self.target.to_owned());
+        self.parameters.push(nick.to_owned());
+        self.parameters.push(reason);
+        self
+    }
+
     pub fn topic(mut self, topic: String) -> Self {
         self.command = CommandType::Name("TOPIC");
         self.parameters.push(self.target.to_owned());
@@ -132,6 +607,12 @@ impl<'a> ResponseBuilder<'a> {
     // Misc
     //
 
+    pub fn ping(mut self, token: &str) -> Self {
+        self.command = CommandType::Name("PING");
+        self.parameters.push(token.to_owned());
+        self
+    }
+
     pub fn pong(mut self, challenge: String) -> Self {
         self.command = CommandType::Name("PONG");
         self.parameters.push(self.source.to_owned());
@@ -139,10 +620,63 @@ impl<'a> ResponseBuilder<'a> {
         self
     }
 
+    pub fn away(mut self, message: Option<String>) -> Self {
+        self.command = CommandType::Name("AWAY");
+        self.parameters.extend(message);
+        self
+    }
+
+    /// Builds a CAP reply, e.g. `CAP nick LS :away-notify`.
+    pub fn cap(mut self, subcommand: &str, capabilities: &str) -> Self {
+        self.command = CommandType::Name("CAP");
+        self.parameters.push(self.target.to_owned());
+        self.parameters.push(subcommand.to_owned());
+        self.parameters.push(capabilities.to_owned());
+        self
+    }
+
+    pub fn nick(mut self) -> Self {
+        self.command = CommandType::Name("NICK");
+        self.parameters.push(self.target.to_owned());
+        self
+    }
+
+    pub fn quit(mut self, message: String) -> Self {
+        self.command = CommandType::Name("QUIT");
+        self.parameters.push(message);
+        self
+    }
+
+    pub fn error(mut self, message: String) -> Self {
+        self.command = CommandType::Name("ERROR");
+        self.parameters.push(message);
+        self
+    }
+
     //
     // Errors
     //
 
+    pub fn err_erroneous_nickname(mut self, nick: &str) -> Self {
+        self.command = CommandType::Code(ERR_ERRONEUSNICKNAME);
+        self.parameters.push(nick.to_owned());
+        self.parameters.push("Erroneous nickname".to_owned());
+        self
+    }
+
+    pub fn err_no_admin_info(mut self) -> Self {
+        self.command = CommandType::Code(ERR_NOADMININFO);
+        self.parameters.push(self.source.to_owned());
+        self.parameters.push("No administrative info available".to_owned());
+        self
+    }
+
+    pub fn err_no_motd(mut self) -> Self {
+        self.command = CommandType::Code(ERR_NOMOTD);
+        self.parameters.push("MOTD File is missing".to_owned());
+        self
+    }
+
     pub fn err_nickname_in_use(mut self, nick: String) -> Self {
         self.command = CommandType::Code(ERR_NICKNAMEINUSE);
         self.parameters.push(nick);
@@ -150,6 +684,214 @@ impl<'a> ResponseBuilder<'a> {
         self
     }
 
+    pub fn err_nick_too_fast(mut self, nick: &str, wait_time: u64) -> Self {
+        self.command = CommandType::Code(ERR_NICKTOOFAST__IRCU);
+        self.parameters.push(nick.to_owned());
+        self.parameters.push(format!("Nick change too fast. Please wait {} seconds.", wait_time));
+        self
+    }
+
+    pub fn err_no_recipient(mut self, command: &str) -> Self {
+        self.command = CommandType::Code(ERR_NORECIPIENT);
+        self.parameters.push(format!("No recipient given ({})", command));
+        self
+    }
+
+    pub fn err_no_origin(mut self) -> Self {
+        self.command = CommandType::Code(ERR_NOORIGIN);
+        self.parameters.push("No origin specified".to_owned());
+        self
+    }
+
+    pub fn err_no_text_to_send(mut self) -> Self {
+        self.command = CommandType::Code(ERR_NOTEXTTOSEND);
+        self.parameters.push("No text to send".to_owned());
+        self
+    }
+
+    pub fn err_no_such_nick(mut self, nick: &str) -> Self {
+        self.command = CommandType::Code(ERR_NOSUCHNICK);
+        self.parameters.push(nick.to_owned());
+        self.parameters.push("No such nick/channel.".to_owned());
+        self
+    }
+
+    pub fn err_too_many_targets(mut self, target: &str) -> Self {
+        self.command = CommandType::Code(ERR_TOOMANYTARGETS);
+        self.parameters.push(target.to_owned());
+        self.parameters.push("Too many targets.".to_owned());
+        self
+    }
+
+    pub fn err_was_no_such_nick(mut self, nick: &str) -> Self {
+        self.command = CommandType::Code(ERR_WASNOSUCHNICK);
+        self.parameters.push(nick.to_owned());
+        self.parameters.push("There was no such nickname.".to_owned());
+        self
+    }
+
+    pub fn err_cannot_send_to_chan(mut self, channel_name: &str) -> Self {
+        self.command = CommandType::Code(ERR_CANNOTSENDTOCHAN);
+        self.parameters.push(channel_name.to_owned());
+        self.parameters.push("Cannot send to channel.".to_owned());
+        self
+    }
+
+    pub fn err_input_too_long(mut self) -> Self {
+        self.command = CommandType::Code(ERR_INPUTTOOLONG__IRCU);
+        self.parameters.push("Input line was too long".to_owned());
+        self
+    }
+
+    pub fn err_unknown_command(mut self, command: &str) -> Self {
+        self.command = CommandType::Code(ERR_UNKNOWNCOMMAND);
+        self.parameters.push(command.to_owned());
+        self.parameters.push("Unknown command".to_owned());
+        self
+    }
+
+    pub fn err_no_nickname_given(mut self) -> Self {
+        self.command = CommandType::Code(ERR_NONICKNAMEGIVEN);
+        self.parameters.push("No nickname given".to_owned());
+        self
+    }
+
+    pub fn err_need_more_params(mut self, command: &str) -> Self {
+        self.command = CommandType::Code(ERR_NEEDMOREPARAMS);
+        self.parameters.push(command.to_owned());
+        self.parameters.push("Not enough parameters.".to_owned());
+        self
+    }
+
+    pub fn err_key_set(mut self, channel_name: &str) -> Self {
+        self.command = CommandType::Code(ERR_KEYSET);
+        self.parameters.push(channel_name.to_owned());
+        self.parameters.push("Channel key already set.".to_owned());
+        self
+    }
+
+    pub fn err_channel_is_full(mut self, channel_name: &str) -> Self {
+        self.command = CommandType::Code(ERR_CHANNELISFULL);
+        self.parameters.push(channel_name.to_owned());
+        self.parameters.push("Cannot join channel (+l).".to_owned());
+        self
+    }
+
+    pub fn err_banned_from_chan(mut self, channel_name: &str) -> Self {
+        self.command = CommandType::Code(ERR_BANNEDFROMCHAN);
+        self.parameters.push(channel_name.to_owned());
+        self.parameters.push("Cannot join channel (+b).".to_owned());
+        self
+    }
+
+    pub fn err_ban_list_full(mut self, channel_name: &str, mode: char) -> Self {
+        self.command = CommandType::Code(ERR_BANLISTFULL);
+        self.parameters.push(channel_name.to_owned());
+        self.parameters.push(mode.to_string());
+        self.parameters.push("Channel list is full.".to_owned());
+        self
+    }
+
+    pub fn err_unknown_mode(mut self, mode: char) -> Self {
+        self.command = CommandType::Code(ERR_UNKNOWNMODE);
+        self.parameters.push(mode.to_string());
+        self.parameters.push("is unknown mode char to me.".to_owned());
+        self
+    }
+
+    pub fn err_invite_only_chan(mut self, channel_name: &str) -> Self {
+        self.command = CommandType::Code(ERR_INVITEONLYCHAN);
+        self.parameters.push(channel_name.to_owned());
+        self.parameters.push("Cannot join channel (+i).".to_owned());
+        self
+    }
+
+    pub fn err_bad_channel_key(mut self, channel_name: &str) -> Self {
+        self.command = CommandType::Code(ERR_BADCHANNELKEY);
+        self.parameters.push(channel_name.to_owned());
+        self.parameters.push("Cannot join channel (+k).".to_owned());
+        self
+    }
+
+    pub fn err_chanop_privs_needed(mut self, channel_name: &str) -> Self {
+        self.command = CommandType::Code(ERR_CHANOPRIVSNEEDED);
+        self.parameters.push(channel_name.to_owned());
+        self.parameters.push("You're not channel operator.".to_owned());
+        self
+    }
+
+    pub fn err_user_not_in_channel(mut self, nick: &str, channel_name: &str) -> Self {
+        self.command = CommandType::Code(ERR_USERNOTINCHANNEL);
+        self.parameters.push(nick.to_owned());
+        self.parameters.push(channel_name.to_owned());
+        self.parameters.push("They aren't on that channel.".to_owned());
+        self
+    }
+
+    pub fn err_user_on_channel(mut self, nick: &str, channel_name: &str) -> Self {
+        self.command = CommandType::Code(ERR_USERONCHANNEL);
+        self.parameters.push(nick.to_owned());
+        self.parameters.push(channel_name.to_owned());
+        self.parameters.push("is already on channel.".to_owned());
+        self
+    }
+
+    pub fn err_umode_unknown_flag(mut self) -> Self {
+        self.command = CommandType::Code(ERR_UMODEUNKNOWNFLAG);
+        self.parameters.push("Unknown MODE flag.".to_owned());
+        self
+    }
+
+    pub fn err_users_dont_match(mut self) -> Self {
+        self.command = CommandType::Code(ERR_USERSDONTMATCH);
+        self.parameters.push("Cannot change mode for other users.".to_owned());
+        self
+    }
+
+    pub fn err_invalid_cap_cmd(mut self, subcommand: &str) -> Self {
+        self.command = CommandType::Code(ERR_INVALIDCAPCMD__UNDERNET);
+        self.parameters.push(subcommand.to_owned());
+        self.parameters.push("Invalid CAP command".to_owned());
+        self
+    }
+
+    pub fn err_not_registered(mut self) -> Self {
+        self.command = CommandType::Code(ERR_NOTREGISTERED);
+        self.parameters.push("You have not registered".to_owned());
+        self
+    }
+
+    pub fn err_already_registered(mut self) -> Self {
+        self.command = CommandType::Code(ERR_ALREADYREGISTERED);
+        self.parameters.push("You may not reregister".to_owned());
+        self
+    }
+
+    pub fn err_passwd_mismatch(mut self) -> Self {
+        self.command = CommandType::Code(ERR_PASSWDMISMATCH);
+        self.parameters.push("Password incorrect".to_owned());
+        self
+    }
+
+    pub fn err_no_oper_host(mut self) -> Self {
+        self.command = CommandType::Code(ERR_NOOPERHOST);
+        self.parameters.push("No O-lines for your host".to_owned());
+        self
+    }
+
+    pub fn err_no_privileges(mut self) -> Self {
+        self.command = CommandType::Code(ERR_NOPRIVILEGES);
+        self.parameters.push("Permission Denied- You're not an IRC operator".to_owned());
+        self
+    }
+
+    pub fn err_bad_chan_mask(mut self, channel_name: &str) -> Self {
+        self.command = CommandType::Code(ERR_BADCHANMASK);
+        self.parameters.push(channel_name.to_owned());
+        self.parameters.push("Bad Channel Mask".to_owned());
+        self
+    }
+
     pub fn err_not_on_channel(mut self, channel_name: String) -> Self {
         self.command = CommandType::Code(ERR_NOTONCHANNEL);
         self.parameters.push(channel_name);
@@ -0,0 +1,65 @@
+/// A nickname that has been checked for allowed characters and length.
+/// 
+/// Nicknames start with a letter or one of ``[]\`_^{|}``,
+/// followed by letters, digits, `-` and the same special characters.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Nickname(String);
+
+impl Nickname {
+
+    /// Special characters allowed anywhere in a nickname
+    const SPECIAL_CHARS: &'static str = "[]\\`_^{|}";
+
+    /// Validates a nickname.
+    /// 
+    /// - `max_len` - The maximum length of the nickname (NICKLEN)
+    pub fn parse(nickname: &str, max_len: usize) -> Option<Nickname> {
+        let is_special = |chr: char| Nickname::SPECIAL_CHARS.contains(chr);
+        let mut chars = nickname.chars();
+        let first = chars.next()?;
+        let is_valid = (first.is_ascii_alphabetic() || is_special(first))
+            && chars.all(|chr| chr.is_ascii_alphanumeric() || chr == '-' || is_special(chr))
+            && nickname.len() <= max_len;
+        if is_valid {
+            Some(Nickname(nickname.to_owned()))
+        } else {
+            None
+        }
+    }
+}
+
+/// A channel name that has been checked for allowed characters and length.
+/// 
+/// Channel names start with `#` and may not contain spaces, commas or control characters.
+#[derive(Clone, PartialEq, Debug)]
+pub struct ChannelName(String);
+
+impl ChannelName {
+
+    /// Validates a channel name.
+    /// 
+    /// - `max_len` - The maximum length of the channel name (CHANNELLEN)
+    pub fn parse(channel_name: &str, max_len: usize) -> Option<ChannelName> {
+        let is_valid = channel_name.starts_with('#')
+            && channel_name.len() > 1
+            && channel_name.len() <= max_len
+            && !channel_name.contains(|chr: char| chr == ' ' || chr == ',' || chr.is_control());
+        if is_valid {
+            Some(ChannelName(channel_name.to_owned()))
+        } else {
+            None
+        }
+    }
+}
+
+impl From<Nickname> for String {
+    fn from(nickname: Nickname) -> String {
+        nickname.0
+    }
+}
+
+impl From<ChannelName> for String {
+    fn from(channel_name: ChannelName) -> String {
+        channel_name.0
+    }
+}
\ No newline at end of file
@@ -1,153 +1,172 @@
-use std::str::Chars;
 use crate::message::*;
 
+/// The maximum length of a message in bytes, including the line ending but excluding tags.
+pub const MAX_MESSAGE_LENGTH: usize = 512;
+
+/// The maximum length of the tags of a message in bytes, including the `@` and the trailing space.
+pub const MAX_TAGS_LENGTH: usize = 8191;
+
+/// The maximum number of parameters of a command.
+pub const MAX_PARAMETERS: usize = 15;
+
+/// An error encountered while parsing a message.
+#[derive(Debug, PartialEq)]
+pub enum ParseError {
+
+    /// The line doesn't contain a command
+    EmptyMessage,
+
+    /// The message exceeds `MAX_MESSAGE_LENGTH`
+    MessageTooLong,
+
+    /// The tags exceed `MAX_TAGS_LENGTH`
+    TagsTooLong,
+
+    /// The command isn't known to the server
+    /// 
+    /// * `0` - Command name
+    UnknownCommand(String),
+
+    /// A required parameter is missing
+    /// 
+    /// * `0` - Command name
+    NeedMoreParams(String),
+
+    /// NICK or WHOIS was sent without a nickname
+    NoNicknameGiven,
+
+    /// PING was sent without a token
+    NoOrigin,
+
+    /// PRIVMSG or NOTICE was sent without a target
+    /// 
+    /// * `0` - Command name
+    NoRecipient(String),
+
+    /// PRIVMSG or NOTICE was sent without a message
+    /// 
+    /// * `0` - Command name
+    NoTextToSend(String),
+}
+
 pub struct IrcMessageParser;
 
 impl IrcMessageParser {
-    pub fn parse(line: &str) -> IrcMessageRequest {
-        let mut chars: Chars = line.chars();
-        let mut tag: Option<IrcMessageTags> = None;
-        let mut prefix: Option<IrcMessagePrefix> = None;
-        let mut command = IrcMessageCommand::None;
-
-        fn parse_tags(chars: &mut Chars) -> IrcMessageTags {
-            let mut tags: Vec<IrcMessageTag> = Vec::new();
-            let mut current_key = String::new();
-            let mut current_val: Option<String> = None;
-            for chr in chars {
-                match chr {
-                    ' ' => {
-                        let tag = IrcMessageTag(current_key, current_val.clone());
-                        tags.push(tag);
-                        break;
-                    },
-                    ';' => {
-                        let tag = IrcMessageTag(current_key, current_val.clone());
-                        tags.push(tag);
-                        current_key = String::new();
-                        current_val = None;
-                    },
-                    '=' => current_val = Some(String::new()),
-                    _ => {
-                        match current_val {
-                            Some(ref mut buf) => buf.push(chr),
-                            None => current_key.push(chr),
-                        };
-                    }
-                }
+    pub fn parse(line: &str) -> Result<IrcMessageRequest, ParseError> {
+
+        /// Splits the string at the first space.
+        fn split_word(line: &str) -> (&str, &str) {
+            match line.find(' ') {
+                Some(pos) => (&line[..pos], &line[pos + 1..]),
+                None => (line, ""),
             }
-            IrcMessageTags::Many(tags)
         }
 
-        fn parse_prefix(chars: &mut Chars) -> IrcMessagePrefix {
-            let mut buf = String::new();
-            for chr in chars {
-                match chr {
-                    ' ' => break,
-                    _ => buf.push(chr),
+        /// Replaces the escape sequences of a tag value.
+        fn unescape_tag_value(value: &str) -> String {
+            let mut buf = String::with_capacity(value.len());
+            let mut chars = value.chars();
+            while let Some(chr) = chars.next() {
+                if chr != '\\' {
+                    buf.push(chr);
+                    continue;
                 }
-            }
-            IrcMessagePrefix(buf)
-        }
 
-        fn parse_command_name(chars: &mut Chars, current_char: char) -> String {
-            let mut command_name = String::new();
-            command_name.push(current_char);
-            for chr in chars {
-                match chr {
-                    ' ' => break,
-                    _ => command_name.push(chr),
+                // A trailing backslash is dropped
+                match chars.next() {
+                    Some(':') => buf.push(';'),
+                    Some('s') => buf.push(' '),
+                    Some('r') => buf.push('\r'),
+                    Some('n') => buf.push('\n'),
+                    Some(chr) => buf.push(chr),
+                    None => break,
                 }
             }
-            command_name
+            buf
         }
 
-        fn parse_command_parameter(chars: &mut Chars) -> Option<String> {
-            let mut parameter = String::new();
-            let mut trailing_parameter = false;
-            for chr in chars {
-                match chr {
-                    ' ' if !trailing_parameter => break,
-                    ':' => trailing_parameter = true,
-                    _ => parameter.push(chr),
-                }
-            }
-            if parameter.is_empty() {
-                None
-            } else {
-                Some(parameter.trim().to_owned())
-            }
+        fn parse_tags(tags: &str) -> IrcMessageTags {
+            let tags = tags
+                .split(';')
+                .filter(|tag| !tag.is_empty())
+                .map(|tag| match tag.find('=') {
+                    Some(pos) => IrcMessageTag(tag[..pos].to_owned(), Some(unescape_tag_value(&tag[pos + 1..]))),
+                    None => IrcMessageTag(tag.to_owned(), None),
+                })
+                .collect();
+            IrcMessageTags::Many(tags)
         }
 
-        fn parse_command_parameters(mut chars: &mut Chars) -> Vec<String> {
+        fn parse_command_parameters(mut line: &str) -> Vec<String> {
             let mut parameters: Vec<String> = Vec::new();
-            while let Some(parameter) = parse_command_parameter(&mut chars) {
-                parameters.push(parameter);
+            loop {
+
+                // Parameters may be separated by more than one space
+                line = line.trim_start_matches(' ');
+                if line.is_empty() {
+                    break;
+                }
+
+                // The trailing parameter may contain spaces and colons.
+                // The last possible parameter is always a trailing one, even without the colon.
+                if line.starts_with(':') || parameters.len() == MAX_PARAMETERS - 1 {
+                    let trailing = line.strip_prefix(':').unwrap_or(line);
+                    parameters.push(trailing.to_owned());
+                    break;
+                }
+
+                // Middle parameters end at the next space
+                let (parameter, rest) = split_word(line);
+                parameters.push(parameter.to_owned());
+                line = rest;
             }
             parameters
         }
 
-        fn parse_command(mut chars: &mut Chars, current_char: char) -> IrcMessageCommand {
-            let command_name = parse_command_name(&mut chars, current_char);
-            let parameters = parse_command_parameters(&mut chars);
+        fn parse_command(command_name: String, parameters: Vec<String>) -> Result<IrcMessageCommand, ParseError> {
 
             macro_rules! extract {
                 ($params:expr; $command:ident $pos:expr => REQ $name:expr) => (
                     $params
-                        .iter()
-                        .nth($pos)
-                        .expect(&format!("Command '{}' MUST include the {}!", stringify!($command), $name))
-                        .to_owned()
+                        .get($pos)
+                        .cloned()
+                        .ok_or_else(|| ParseError::NeedMoreParams(stringify!($command).to_owned()))?
                 );
                 ($params:expr; $command:ident $pos:expr => OPT $name:expr) => (
                     $params
-                        .iter()
-                        .nth($pos)
-                        .map_or(None, |param| Some(param.to_owned()))
+                        .get($pos)
+                        .cloned()
                 );
             }
 
-            macro_rules! validate {
-                ($params:expr; $command:ident $pos:expr => MUST EQ $expected:expr; $message:expr) => {{
-                    let tmp = $params
-                        .iter()
-                        .nth($pos)
-                        .expect(&format!("Failed validation for command '{}'", stringify!($command)))
-                        == $expected;
-                    if !tmp {
-                        panic!("Failed validation assertion for command '{}': {}", stringify!($command), $message)
-                    }
-                }};
-                ($params:expr; $command:ident $pos:expr => SHOULD EQ $expected:expr; $message:expr) => {{
-                    let tmp = $params
-                        .iter()
-                        .nth($pos)
-                        .expect(&format!("Failed validation for command '{}'", stringify!($command)))
-                        == $expected;
-                    if !tmp {
-                        // println!("Failed optional validation for command '{}': {}", stringify!($command), $message)
-                    }
-                }};
-            }
-
-            match command_name.as_ref() {
+            let command = match command_name.as_ref() {
+                "PASS" => {
+                    let password = extract!(parameters; PASS 0 => OPT "password");
+                    IrcMessageCommand::Pass(password)
+                }
                 "NICK" => {
-                    let nickname = extract!(parameters; NICK 0 => REQ "nickname");
+                    let nickname = extract!(parameters; NICK 0 => OPT "nickname")
+                        .filter(|nickname| !nickname.is_empty())
+                        .ok_or(ParseError::NoNicknameGiven)?;
                     IrcMessageCommand::Nick(nickname)
                 },
                 "USER" => {
+                    // The second and third parameters are unused
                     let username = extract!(parameters; USER 0 => REQ "username");
-                    validate!(parameters; USER 1 => SHOULD EQ "0"; "Second parameter should equal '0'");
-                    validate!(parameters; USER 2 => SHOULD EQ "*"; "Third parameter should equal '*'");
-                    let realname = extract!(parameters; USER 3 => OPT "realname");
-                    IrcMessageCommand::User(username, realname)
+                    let realname = extract!(parameters; USER 3 => REQ "realname");
+                    IrcMessageCommand::User(username, Some(realname))
+                }
+                "CAP" => {
+                    let subcommand = extract!(parameters; CAP 0 => REQ "subcommand");
+                    let capabilities = extract!(parameters; CAP 1 => OPT "capabilities");
+                    IrcMessageCommand::Cap(subcommand, capabilities)
                 }
                 "JOIN" => {
                     let channels = extract!(parameters; JOIN 0 => REQ "channel names")
                         .split(',')
                         .map(|s| s.to_owned())
                         .collect();
-                    let keys = extract!(parameters; PART 1 => OPT "channel keys")
+                    let keys = extract!(parameters; JOIN 1 => OPT "channel keys")
                         .map(|keys| {
                             keys.split(',')
                             .map(|s| s.to_owned())
@@ -163,48 +182,259 @@ impl IrcMessageParser {
                     let message = extract!(parameters; PART 1 => OPT "message");
                     IrcMessageCommand::Part(channels, message)
                 }
-                "PRIVMSG" => {
-                    let target = extract!(parameters; PRIVMSG 0 => REQ "target");
-                    let message = extract!(parameters; PRIVMSG 1 => REQ "message");
-                    IrcMessageCommand::Privmsg(target, message)
+                "TOPIC" => {
+                    let channel = extract!(parameters; TOPIC 0 => REQ "channel name");
+                    let topic = extract!(parameters; TOPIC 1 => OPT "topic");
+                    IrcMessageCommand::Topic(channel, topic)
+                }
+                "PRIVMSG" | "NOTICE" => {
+                    let target = extract!(parameters; PRIVMSG 0 => OPT "target")
+                        .filter(|target| !target.is_empty())
+                        .ok_or_else(|| ParseError::NoRecipient(command_name.clone()))?;
+                    let message = extract!(parameters; PRIVMSG 1 => OPT "message")
+                        .filter(|message| !message.is_empty())
+                        .ok_or_else(|| ParseError::NoTextToSend(command_name.clone()))?;
+                    if command_name == "PRIVMSG" {
+                        IrcMessageCommand::Privmsg(target, message)
+                    } else {
+                        IrcMessageCommand::Notice(target, message)
+                    }
+                }
+                "INVITE" => {
+                    let invite = match extract!(parameters; INVITE 0 => OPT "nickname") {
+                        Some(nickname) => Some((nickname, extract!(parameters; INVITE 1 => REQ "channel name"))),
+                        None => None,
+                    };
+                    IrcMessageCommand::Invite(invite)
+                }
+                "KICK" => {
+                    let channel = extract!(parameters; KICK 0 => REQ "channel name");
+                    let nicknames = extract!(parameters; KICK 1 => REQ "nicknames")
+                        .split(',')
+                        .map(|s| s.to_owned())
+                        .collect();
+                    let reason = extract!(parameters; KICK 2 => OPT "reason");
+                    IrcMessageCommand::Kick(channel, nicknames, reason)
+                }
+                "MODE" => {
+                    let target = extract!(parameters; MODE 0 => REQ "target");
+                    let modes = extract!(parameters; MODE 1 => OPT "mode string");
+                    let arguments = parameters.iter().skip(2).cloned().collect();
+                    IrcMessageCommand::Mode(target, modes, arguments)
+                }
+                "NAMES" => {
+                    let channels = extract!(parameters; NAMES 0 => OPT "channel names")
+                        .map(|channels| {
+                            channels.split(',')
+                            .map(|s| s.to_owned())
+                            .collect()
+                        });
+                    IrcMessageCommand::Names(channels)
+                }
+                "LIST" => {
+                    let filters = extract!(parameters; LIST 0 => OPT "filters")
+                        .map(|filters| {
+                            filters.split(',')
+                            .map(|s| s.to_owned())
+                            .collect()
+                        });
+                    IrcMessageCommand::List(filters)
                 }
                 "WHO" => {
-                    let channel = extract!(parameters; WHO 0 => REQ "channel name");
-                    IrcMessageCommand::Who(channel)
+                    let mask = extract!(parameters; WHO 0 => OPT "mask").unwrap_or_else(|| "*".to_owned());
+                    let options = extract!(parameters; WHO 1 => OPT "options");
+                    IrcMessageCommand::Who(mask, options)
+                }
+                "WHOIS" => {
+                    // The nicknames are always the last parameter, the optional first one is a server name
+                    let nicknames = extract!(parameters; WHOIS parameters.len().max(1) - 1 => OPT "nicknames")
+                        .filter(|nicknames| !nicknames.is_empty())
+                        .ok_or(ParseError::NoNicknameGiven)?
+                        .split(',')
+                        .map(|s| s.to_owned())
+                        .collect();
+                    IrcMessageCommand::Whois(nicknames)
+                }
+                "USERHOST" => {
+                    let nicknames = parameters.iter()
+                        .flat_map(|parameter| parameter.split_whitespace())
+                        .map(|s| s.to_owned())
+                        .collect();
+                    IrcMessageCommand::Userhost(nicknames)
                 }
+                "ISON" => {
+                    // The nicknames are separate parameters, but some clients send them as a single trailing parameter
+                    let nicknames = parameters.iter()
+                        .flat_map(|parameter| parameter.split_whitespace())
+                        .map(|s| s.to_owned())
+                        .collect();
+                    IrcMessageCommand::Ison(nicknames)
+                }
+                "WHOWAS" => {
+                    let nickname = extract!(parameters; WHOWAS 0 => REQ "nickname");
+                    let count = extract!(parameters; WHOWAS 1 => OPT "count")
+                        .and_then(|count| count.parse().ok());
+                    IrcMessageCommand::Whowas(nickname, count)
+                }
+                "AWAY" => {
+                    let message = extract!(parameters; AWAY 0 => OPT "message");
+                    IrcMessageCommand::Away(message)
+                }
+                "OPER" => {
+                    let name = extract!(parameters; OPER 0 => REQ "name");
+                    let password = extract!(parameters; OPER 1 => REQ "password");
+                    IrcMessageCommand::Oper(name, password)
+                }
+                "KILL" => {
+                    let nickname = extract!(parameters; KILL 0 => REQ "nickname");
+                    let reason = extract!(parameters; KILL 1 => OPT "reason");
+                    IrcMessageCommand::Kill(nickname, reason)
+                }
+                "VERSION" => IrcMessageCommand::Version,
+                "MOTD" => IrcMessageCommand::Motd,
+                "LUSERS" => IrcMessageCommand::Lusers,
+                "TIME" => IrcMessageCommand::Time,
+                "INFO" => IrcMessageCommand::Info,
+                "ADMIN" => IrcMessageCommand::Admin,
+                "REHASH" => IrcMessageCommand::Rehash,
+                "DIE" => IrcMessageCommand::Die,
                 "PING" => {
-                    let challenge = extract!(parameters; JOIN 0 => REQ "challenge");
-                    IrcMessageCommand::Ping(challenge)   
+                    let challenge = extract!(parameters; PING 0 => OPT "challenge")
+                        .filter(|challenge| !challenge.is_empty())
+                        .ok_or(ParseError::NoOrigin)?;
+                    IrcMessageCommand::Ping(challenge)
                 }
-                _ => {
-                    println!("Unimplemented: {}", command_name);
-                    IrcMessageCommand::None
+                "PONG" => IrcMessageCommand::Pong,
+                "QUIT" => {
+                    let message = extract!(parameters; QUIT 0 => OPT "message");
+                    IrcMessageCommand::Quit(message)
                 }
-            }
+                _ => return Err(ParseError::UnknownCommand(command_name)),
+            };
+
+            Ok(command)
         }
-        
-        loop {
-            let chr = chars.next();
-            if chr.is_none() {
-                break;
+
+        // Accept both CRLF and bare LF line endings
+        let mut line = line.trim_end_matches(['\r', '\n']);
+
+        // Tags have a separate length budget
+        let mut tags: Option<IrcMessageTags> = None;
+        if line.starts_with('@') {
+            let (tag_str, rest) = split_word(&line[1..]);
+            if tag_str.len() + 2 > MAX_TAGS_LENGTH {
+                return Err(ParseError::TagsTooLong);
             }
+            tags = Some(parse_tags(tag_str));
+            line = rest.trim_start_matches(' ');
+        }
 
-            match chr.unwrap() {
+        // The rest of the message has to fit into a single line, including the line ending
+        if line.len() + 2 > MAX_MESSAGE_LENGTH {
+            return Err(ParseError::MessageTooLong);
+        }
 
-                // Tag
-                '@' => tag = Some(parse_tags(&mut chars)),
+        // Prefix
+        let mut prefix: Option<IrcMessagePrefix> = None;
+        if line.starts_with(':') {
+            let (prefix_str, rest) = split_word(&line[1..]);
+            prefix = Some(IrcMessagePrefix(prefix_str.to_owned()));
+            line = rest.trim_start_matches(' ');
+        }
 
-                // Prefix
-                ':' => prefix = Some(parse_prefix(&mut chars)),
+        // Command names are case insensitive
+        let (command_name, parameters) = split_word(line);
+        if command_name.is_empty() {
+            return Err(ParseError::EmptyMessage);
+        }
+        let command = parse_command(command_name.to_ascii_uppercase(), parse_command_parameters(parameters))?;
 
-                // Message
-                chr => {
-                    command = parse_command(&mut chars, chr);
-                    break;
-                }
-            };
+        Ok(IrcMessageRequest::new(command, prefix, tags))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(line: &str) -> Result<IrcMessageCommand, ParseError> {
+        IrcMessageParser::parse(line).map(|request| request.command)
+    }
+
+    fn privmsg(target: &str, message: &str) -> IrcMessageCommand {
+        IrcMessageCommand::Privmsg(target.to_owned(), message.to_owned())
+    }
+
+    #[test]
+    fn test_commands() {
+        let cases = vec![
+            ("NICK alice\r\n", Ok(IrcMessageCommand::Nick("alice".to_owned()))),
+            ("nick alice\n", Ok(IrcMessageCommand::Nick("alice".to_owned()))),
+            ("USER a 0 * :Real Name\r\n", Ok(IrcMessageCommand::User("a".to_owned(), Some("Real Name".to_owned())))),
+            ("PING :token\r\n", Ok(IrcMessageCommand::Ping("token".to_owned()))),
+            ("WHOIS irc.example.org alice,bob\r\n", Ok(IrcMessageCommand::Whois(vec!["alice".to_owned(), "bob".to_owned()]))),
+            ("PRIVMSG #chat :hello: world  :x \r\n", Ok(privmsg("#chat", "hello: world  :x "))),
+            ("PRIVMSG #chat hel:lo\r\n", Ok(privmsg("#chat", "hel:lo"))),
+            ("PRIVMSG  #chat   :spaces\r\n", Ok(privmsg("#chat", "spaces"))),
+            (":alice!a@host PRIVMSG #chat :prefixed\r\n", Ok(privmsg("#chat", "prefixed"))),
+            ("@time=12;+tag=a\\sb :alice PRIVMSG #chat :tagged\r\n", Ok(privmsg("#chat", "tagged"))),
+        ];
+        for (line, expected) in cases {
+            assert_eq!(parse(line), expected, "{:?}", line);
+        }
+    }
+
+    #[test]
+    fn test_errors() {
+        let cases = vec![
+            ("", ParseError::EmptyMessage),
+            ("\r\n", ParseError::EmptyMessage),
+            ("   \n", ParseError::EmptyMessage),
+            ("FOO bar\r\n", ParseError::UnknownCommand("FOO".to_owned())),
+            ("NICK\r\n", ParseError::NoNicknameGiven),
+            ("NICK :\r\n", ParseError::NoNicknameGiven),
+            ("WHOIS\r\n", ParseError::NoNicknameGiven),
+            ("PING\r\n", ParseError::NoOrigin),
+            ("PING :\r\n", ParseError::NoOrigin),
+            ("USER a 0 *\r\n", ParseError::NeedMoreParams("USER".to_owned())),
+            ("JOIN\r\n", ParseError::NeedMoreParams("JOIN".to_owned())),
+            ("PRIVMSG\r\n", ParseError::NoRecipient("PRIVMSG".to_owned())),
+            ("PRIVMSG #chat\r\n", ParseError::NoTextToSend("PRIVMSG".to_owned())),
+            ("PRIVMSG #chat :\r\n", ParseError::NoTextToSend("PRIVMSG".to_owned())),
+            ("NOTICE\r\n", ParseError::NoRecipient("NOTICE".to_owned())),
+            ("NOTICE #chat\r\n", ParseError::NoTextToSend("NOTICE".to_owned())),
+        ];
+        for (line, expected) in cases {
+            assert_eq!(parse(line), Err(expected), "{:?}", line);
         }
+    }
+
+    #[test]
+    fn test_parameter_limit() {
+
+        // The 15th parameter takes the rest of the line
+        let arguments: Vec<String> = (1..=18).map(|i| format!("p{}", i)).collect();
+        let line = format!("MODE #chat +i {}\r\n", arguments.join(" "));
+        let mut expected: Vec<String> = arguments[..12].to_vec();
+        expected.push(arguments[12..].join(" "));
+        assert_eq!(parse(&line), Ok(IrcMessageCommand::Mode("#chat".to_owned(), Some("+i".to_owned()), expected)));
+    }
+
+    #[test]
+    fn test_length_limits() {
+
+        // 512 bytes including the line ending
+        let prefix = "PRIVMSG #chat :";
+        let longest = format!("{}{}\r\n", prefix, "x".repeat(MAX_MESSAGE_LENGTH - prefix.len() - 2));
+        assert_eq!(longest.len(), MAX_MESSAGE_LENGTH);
+        assert!(parse(&longest).is_ok());
+        let too_long = format!("{}{}\r\n", prefix, "x".repeat(MAX_MESSAGE_LENGTH - prefix.len() - 1));
+        assert_eq!(parse(&too_long), Err(ParseError::MessageTooLong));
 
-        IrcMessageRequest::new(command, prefix, tag)
+        // Tags have a separate budget, including the '@' and the space
+        let tags = "a".repeat(MAX_TAGS_LENGTH - 2);
+        assert_eq!(parse(&format!("@{} PING x\r\n", tags)), Ok(IrcMessageCommand::Ping("x".to_owned())));
+        assert_eq!(parse(&format!("@{}a PING x\r\n", tags)), Err(ParseError::TagsTooLong));
+        assert!(parse(&format!("@{} {}", tags, longest)).is_ok());
     }
 }
\ No newline at end of file
@@ -1,13 +1,21 @@
 mod user;
+mod user_modes;
 mod channel;
+mod channel_modes;
 mod user_list;
 mod channel_list;
+mod whowas_list;
+mod isupport;
 mod action;
 mod server;
 
 pub use self::user::User;
+pub use self::user_modes::UserModes;
 pub use self::channel::Channel;
+pub use self::channel_modes::{ChannelModes, ListEntry};
 pub use self::user_list::UserList;
 pub use self::channel_list::ChannelList;
+pub use self::whowas_list::WhowasList;
+pub use self::isupport::ISupport;
 pub use self::action::IrcAction;
 pub use self::server::Server;
\ No newline at end of file
@@ -1,3 +1,5 @@
+use crate::parser::ParseError;
+
 #[derive(Debug)]
 pub enum IrcAction {
 
@@ -8,6 +10,14 @@ pub enum IrcAction {
     /// User / Connect
     UserConnect(),
 
+    /// User / Registration Timeout
+    UserRegistrationTimeout(),
+
+    /// User / Set Password
+    /// 
+    /// * `0` - Password
+    UserSetPassword(Option<String>),
+
     /// User / Set Nick
     /// 
     /// * `0` - Nickname
@@ -19,6 +29,12 @@ pub enum IrcAction {
     /// * `1` - Realname
     UserSetNames(String, Option<String>),
 
+    /// User / Capability
+    /// 
+    /// * `0` - Subcommand
+    /// * `1` - Capabilities
+    UserCapability(String, Option<String>),
+
     /// User / Join Channel
     /// 
     /// * `0` - Channel name
@@ -30,14 +46,126 @@ pub enum IrcAction {
     /// * `0` - Channel name
     UserPartChannel(String, Option<String>),
 
+    /// User / Mode
+    /// 
+    /// * `0` - Nickname
+    /// * `1` - Mode string
+    UserMode(String, Option<String>),
+
+    /// User / Whois
+    /// 
+    /// * `0` - Nickname(s)
+    UserWhois(Vec<String>),
+
+    /// User / Userhost
+    /// 
+    /// * `0` - Nicknames
+    UserUserhost(Vec<String>),
+
+    /// User / Ison
+    /// 
+    /// * `0` - Nicknames
+    UserIson(Vec<String>),
+
+    /// User / Whowas
+    /// 
+    /// * `0` - Nickname
+    /// * `1` - Maximum number of entries
+    UserWhowas(String, Option<usize>),
+
+    /// User / Away
+    /// 
+    /// * `0` - Away message
+    UserAway(Option<String>),
+
+    /// User / Oper
+    /// 
+    /// * `0` - Operator name
+    /// * `1` - Password
+    UserOper(String, String),
+
+    /// User / Kill
+    /// 
+    /// * `0` - Nickname
+    /// * `1` - Reason
+    UserKill(String, Option<String>),
+
+    //
+    // Server
+    //
+
+    /// Server / Version
+    ServerVersion(),
+
+    /// Server / Message of the day
+    ServerMotd(),
+
+    /// Server / User and channel counts
+    ServerLusers(),
+
+    /// Server / Local time
+    ServerTime(),
+
+    /// Server / Software information
+    ServerInfo(),
+
+    /// Server / Administrative information
+    ServerAdmin(),
+
+    /// Server / Rehash
+    ServerRehash(),
+
+    /// Server / Die
+    ServerDie(),
+
     //
     // Channels
     //
+
+    /// Channel / Topic
+    /// 
+    /// * `0` - Channel name
+    /// * `1` - New topic
+    ChannelTopic(String, Option<String>),
     
-    /// Channel / List Users
+    /// Channel / Invite
+    /// 
+    /// * `0` - Nickname
+    /// * `1` - Channel name
+    ChannelInvite(String, String),
+
+    /// Channel / List Invites
+    ChannelListInvites(),
+
+    /// Channel / Kick
+    /// 
+    /// * `0` - Channel name
+    /// * `1` - Nickname(s)
+    /// * `2` - Reason
+    ChannelKick(String, Vec<String>, Option<String>),
+
+    /// Channel / Mode
     /// 
     /// * `0` - Channel name
-    ChannelListUsers(String),
+    /// * `1` - Mode string
+    /// * `2` - Mode arguments
+    ChannelMode(String, Option<String>, Vec<String>),
+
+    /// Channel / List Users
+    /// 
+    /// * `0` - Channel name(s)
+    ChannelListUsers(Option<Vec<String>>),
+
+    /// Channel / List Channels
+    /// 
+    /// * `0` - Channel names, masks and ELIST filters
+    ChannelListChannels(Option<Vec<String>>),
+
+    /// Channel / Who
+    /// 
+    /// * `0` - Mask
+    /// * `1` - WHOX fields and query token
+    ChannelWho(String, Option<String>),
 
     //
     // Messaging
@@ -49,6 +177,12 @@ pub enum IrcAction {
     /// * `1` - Message
     Privmsg(String, String),
 
+    /// Notice
+    /// 
+    /// * `0` - Target
+    /// * `1` - Message
+    Notice(String, String),
+
     //
     // Misc
     //
@@ -58,6 +192,40 @@ pub enum IrcAction {
     /// * `0` - Ping id
     Pong(String),
 
+    /// User / Reply to a server PING
+    UserPong(),
+
+    /// Invalid message
+    /// 
+    /// * `0` - The reason why the message couldn't be parsed
+    InvalidMessage(ParseError),
+
     /// Disconnect
-    Disconnect(),
+    /// 
+    /// * `0` - Quit message
+    Disconnect(Option<String>),
+}
+
+impl IrcAction {
+
+    /// Tests whether the action requires the user to be registered.
+    pub fn requires_registration(&self) -> bool {
+        !matches!(self,
+            IrcAction::UserConnect() |
+            IrcAction::UserRegistrationTimeout() |
+            IrcAction::UserSetPassword(_) |
+            IrcAction::UserSetNick(_) |
+            IrcAction::UserSetNames(_, _) |
+            IrcAction::UserCapability(_, _) |
+            IrcAction::Pong(_) |
+            IrcAction::UserPong() |
+            IrcAction::InvalidMessage(_) |
+            IrcAction::Disconnect(_)
+        )
+    }
+
+    /// Tests whether the action was caused by a message from the client.
+    pub fn is_client_message(&self) -> bool {
+        !matches!(self, IrcAction::UserConnect() | IrcAction::UserRegistrationTimeout())
+    }
 }
\ No newline at end of file
@@ -1,48 +1,158 @@
 use serde_derive::{Serialize, Deserialize};
 
-#[derive(Serialize, Deserialize)]
+use super::ChannelModes;
+use crate::name::ChannelName;
+
+/// All member status modes, from the highest to the lowest rank.
+/// 
+/// - `q` - Founder
+/// - `a` - Protected
+/// - `o` - Operator
+/// - `h` - Half-operator
+/// - `v` - Voice
+pub const STATUS_MODES: [char; 5] = ['q', 'a', 'o', 'h', 'v'];
+
+#[derive(Serialize, Deserialize, Clone)]
 pub struct ChannelUserInfo {
     client_id: usize,
+    status: u8,
 }
 
 impl ChannelUserInfo {
     pub fn new(client_id: usize) -> Self {
         Self {
-            client_id
+            client_id,
+            status: 0,
         }
     }
 
     pub fn client_id(&self) -> usize {
         self.client_id
     }
+
+    /// Gets the status bit of a member status mode.
+    /// 
+    /// Higher ranked modes have higher bits, so comparing the status
+    /// of two members compares their highest ranked modes.
+    fn status_bit(mode: char) -> u8 {
+        STATUS_MODES.iter()
+            .position(|status_mode| *status_mode == mode)
+            .map_or(0, |i| 1 << (STATUS_MODES.len() - 1 - i))
+    }
+
+    pub fn has_status(&self, mode: char) -> bool {
+        self.status & ChannelUserInfo::status_bit(mode) != 0
+    }
+
+    /// Sets or unsets a member status mode.
+    /// 
+    /// Returns `true` if the status has changed.
+    pub fn set_status(&mut self, mode: char, value: bool) -> bool {
+        let previous = self.status;
+        if value {
+            self.status |= ChannelUserInfo::status_bit(mode);
+        } else {
+            self.status &= !ChannelUserInfo::status_bit(mode);
+        }
+        self.status != previous
+    }
+
+    /// Tests whether the member ranks at least as high as the given status mode.
+    pub fn has_rank(&self, mode: char) -> bool {
+        self.status >= ChannelUserInfo::status_bit(mode)
+    }
+
+    /// Tests whether the member is a channel operator or higher.
+    pub fn is_operator(&self) -> bool {
+        self.has_rank('o')
+    }
+
+    /// Tests whether the member is a half-operator or higher.
+    pub fn is_half_operator(&self) -> bool {
+        self.has_rank('h')
+    }
+
+    /// Tests whether the member may speak in a moderated channel.
+    pub fn can_speak(&self) -> bool {
+        self.status != 0
+    }
+
+    /// Gets the prefix of the highest ranked status mode of the member.
+    /// 
+    /// - `status_modes` - The enabled status modes and their prefixes
+    pub fn prefix(&self, status_modes: &[(char, char)]) -> String {
+        STATUS_MODES.iter()
+            .filter(|mode| self.has_status(**mode))
+            .filter_map(|mode| status_modes.iter().find(|(status_mode, _)| status_mode == mode))
+            .map(|(_, prefix)| prefix.to_string())
+            .next()
+            .unwrap_or_default()
+    }
 }
 
 #[derive(Serialize, Deserialize)]
 pub struct Channel {
     pub name: String,
     pub topic: Option<String>,
+    #[serde(default)] pub topic_setter: Option<String>,
+    #[serde(default)] pub topic_time: Option<u64>,
+    #[serde(default = "crate::time::timestamp")] pub created: u64,
+    #[serde(default)] pub modes: ChannelModes,
     #[serde(skip)] users: Vec<ChannelUserInfo>,
 }
 
 impl Channel {
-    pub fn new(name: String) -> Self {
+    pub fn new(name: ChannelName) -> Self {
         Self {
-            name,
+            name: name.into(),
             topic: None,
+            topic_setter: None,
+            topic_time: None,
+            created: crate::time::timestamp(),
+            modes: ChannelModes::default(),
             users: Vec::new(),
         }
     }
 
+    /// Sets or clears the topic and records who changed it.
+    /// 
+    /// - `topic` - The new topic, or `None` to clear it
+    /// - `setter` - The hostmask of the user changing the topic
+    pub fn set_topic(&mut self, topic: Option<String>, setter: String) {
+        self.topic = topic;
+        self.topic_setter = Some(setter);
+        self.topic_time = Some(crate::time::timestamp());
+    }
+
     pub fn users(&self) -> &Vec<ChannelUserInfo> {
         &self.users
     }
 
+    pub fn member(&self, client_id: usize) -> Option<&ChannelUserInfo> {
+        self.users.iter().find(|user| user.client_id() == client_id)
+    }
+
+    pub fn member_mut(&mut self, client_id: usize) -> Option<&mut ChannelUserInfo> {
+        self.users.iter_mut().find(|user| user.client_id() == client_id)
+    }
+
+    /// Gets the status prefix of a member, or an empty string.
+    pub fn prefix(&self, client_id: usize, status_modes: &[(char, char)]) -> String {
+        self.member(client_id).map(|member| member.prefix(status_modes)).unwrap_or_default()
+    }
+
     pub fn contains(&self, client_id: usize) -> bool {
         self.users.iter().any(|user| user.client_id() == client_id)
     }
 
     pub fn join_user(&mut self, client_id: usize) {
-        let user_info = ChannelUserInfo::new(client_id);
+        let mut user_info = ChannelUserInfo::new(client_id);
+
+        // The first user in a channel becomes channel operator
+        if self.users.is_empty() {
+            user_info.set_status('o', true);
+        }
+
         self.users.push(user_info);
     }
 
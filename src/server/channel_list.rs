@@ -1,23 +1,66 @@
 use super::Channel;
+use crate::casemapping::Casemapping;
 use serde_derive::{Serialize, Deserialize};
 
 #[derive(Serialize, Deserialize)]
 pub struct ChannelList {
     channels: Vec<Channel>,
+    #[serde(skip)]
+    casemapping: Casemapping,
 }
 
 impl ChannelList {
-    pub fn new() -> Self {
+    pub fn new(casemapping: Casemapping) -> Self {
         Self {
             channels: Vec::new(),
+            casemapping,
         }
     }
 
+    /// Sets the casemapping used to compare channel names, e.g. after restoring the state.
+    pub fn set_casemapping(&mut self, casemapping: Casemapping) {
+        self.casemapping = casemapping;
+    }
+
     pub fn add(&mut self, channel: Channel) {
         self.channels.push(channel);
     }
 
+    pub fn iter(&self) -> impl Iterator<Item = &Channel> {
+        self.channels.iter()
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut Channel> {
+        self.channels.iter_mut()
+    }
+
+    /// Tests whether two users share at least one channel.
+    pub fn shares_channel(&self, client_id: usize, other_client_id: usize) -> bool {
+        self.channels.iter().any(|channel| channel.contains(client_id) && channel.contains(other_client_id))
+    }
+
+    /// Gets every other user sharing at least one channel with a user, without duplicates.
+    pub fn peers(&self, client_id: usize) -> Vec<usize> {
+        let mut peers: Vec<usize> = Vec::new();
+        for channel in self.channels.iter().filter(|channel| channel.contains(client_id)) {
+            for user_info in channel.users() {
+                if user_info.client_id() != client_id && !peers.contains(&user_info.client_id()) {
+                    peers.push(user_info.client_id());
+                }
+            }
+        }
+        peers
+    }
+
+    /// Finds a channel by name, ignoring case.
     pub fn find(&mut self, channel_name: &str) -> Option<&mut Channel> {
-        self.channels.iter_mut().find(|channel| channel.name == channel_name)
+        let casemapping = self.casemapping;
+        self.channels.iter_mut().find(|channel| casemapping.equals(&channel.name, channel_name))
+    }
+
+    /// Finds a channel by name, ignoring case.
+    pub fn get(&self, channel_name: &str) -> Option<&Channel> {
+        let casemapping = self.casemapping;
+        self.channels.iter().find(|channel| casemapping.equals(&channel.name, channel_name))
     }
 }
\ No newline at end of file
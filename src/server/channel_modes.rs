@@ -0,0 +1,222 @@
+use serde_derive::{Serialize, Deserialize};
+
+use crate::casemapping::Casemapping;
+
+/// An entry of a list mode, such as a ban.
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ListEntry {
+    pub mask: String,
+    pub setter: String,
+    pub time: u64,
+}
+
+impl ListEntry {
+    pub fn new(mask: String, setter: String) -> Self {
+        Self {
+            mask,
+            setter,
+            time: crate::time::timestamp(),
+        }
+    }
+}
+
+/// The modes of a channel.
+#[derive(Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct ChannelModes {
+
+    /// `+n` - No messages from users outside the channel
+    pub no_external_messages: bool,
+
+    /// `+t` - Only channel operators may change the topic
+    pub protected_topic: bool,
+
+    /// `+m` - Only voiced users and channel operators may speak
+    pub moderated: bool,
+
+    /// `+s` - The channel is hidden from users outside the channel
+    pub secret: bool,
+
+    /// `+p` - The channel is private
+    pub private: bool,
+
+    /// `+i` - Users must be invited to join
+    pub invite_only: bool,
+
+    /// `+k` - Users must provide the key to join
+    pub key: Option<String>,
+
+    /// `+l` - The maximum number of users in the channel
+    pub limit: Option<usize>,
+
+    /// `+b` - Users matching these masks may neither join nor speak
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub bans: Vec<ListEntry>,
+
+    /// `+e` - Users matching these masks are exempt from bans
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub exceptions: Vec<ListEntry>,
+
+    /// `+I` - Users matching these masks may join without an invite
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub invite_exceptions: Vec<ListEntry>,
+}
+
+impl ChannelModes {
+
+    /// Modes that never take a parameter.
+    pub const SIMPLE_MODES: &'static str = "imnpst";
+
+    /// Modes that take a parameter when set.
+    pub const PARAMETER_MODES: &'static str = "kl";
+
+    /// Modes that manage a list of masks.
+    pub const LIST_MODES: &'static str = "beI";
+
+    /// Gets all supported channel modes, including the member status modes.
+    /// 
+    /// Returns all modes and the modes taking a parameter, as advertised in RPL_MYINFO.
+    pub fn supported_modes(status_modes: &[(char, char)]) -> (String, String) {
+        let status_modes: String = status_modes.iter().map(|(mode, _)| mode).collect();
+        let mut all_modes: Vec<char> = [ChannelModes::SIMPLE_MODES, ChannelModes::PARAMETER_MODES, ChannelModes::LIST_MODES, &status_modes].concat().chars().collect();
+        let mut parameter_modes: Vec<char> = [ChannelModes::PARAMETER_MODES, ChannelModes::LIST_MODES, &status_modes].concat().chars().collect();
+        all_modes.sort();
+        parameter_modes.sort();
+        (all_modes.into_iter().collect(), parameter_modes.into_iter().collect())
+    }
+
+    /// Gets a mutable reference to a mode that never takes a parameter.
+    fn simple_mode(&mut self, mode: char) -> Option<&mut bool> {
+        match mode {
+            'n' => Some(&mut self.no_external_messages),
+            't' => Some(&mut self.protected_topic),
+            'm' => Some(&mut self.moderated),
+            's' => Some(&mut self.secret),
+            'p' => Some(&mut self.private),
+            'i' => Some(&mut self.invite_only),
+            _ => None,
+        }
+    }
+
+    /// Sets or unsets a mode that never takes a parameter.
+    /// 
+    /// Returns `true` if the mode has changed.
+    pub fn set_simple_mode(&mut self, mode: char, value: bool) -> bool {
+        match self.simple_mode(mode) {
+            Some(current) if *current != value => {
+                *current = value;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Gets the entries of a list mode.
+    pub fn list(&self, mode: char) -> &Vec<ListEntry> {
+        match mode {
+            'b' => &self.bans,
+            'e' => &self.exceptions,
+            'I' => &self.invite_exceptions,
+            _ => panic!("Not a list mode: {}", mode),
+        }
+    }
+
+    fn list_mut(&mut self, mode: char) -> &mut Vec<ListEntry> {
+        match mode {
+            'b' => &mut self.bans,
+            'e' => &mut self.exceptions,
+            'I' => &mut self.invite_exceptions,
+            _ => panic!("Not a list mode: {}", mode),
+        }
+    }
+
+    /// Gets the combined number of entries of all list modes.
+    pub fn list_entry_count(&self) -> usize {
+        self.bans.len() + self.exceptions.len() + self.invite_exceptions.len()
+    }
+
+    /// Adds an entry to a list mode.
+    /// 
+    /// Returns `false` if the mask is already on the list.
+    pub fn add_to_list(&mut self, mode: char, entry: ListEntry, casemapping: Casemapping) -> bool {
+        let list = self.list_mut(mode);
+        if list.iter().any(|existing| casemapping.equals(&existing.mask, &entry.mask)) {
+            return false;
+        }
+        list.push(entry);
+        true
+    }
+
+    /// Removes an entry from a list mode.
+    /// 
+    /// Returns the removed entry, if the mask was on the list.
+    pub fn remove_from_list(&mut self, mode: char, mask: &str, casemapping: Casemapping) -> Option<ListEntry> {
+        let list = self.list_mut(mode);
+        list.iter()
+            .position(|entry| casemapping.equals(&entry.mask, mask))
+            .map(|i| list.remove(i))
+    }
+
+    /// Tests whether a `nick!user@host` mask matches any entry of a list mode.
+    fn list_matches(&self, mode: char, hostmask: &str, casemapping: Casemapping) -> bool {
+        self.list(mode).iter().any(|entry| crate::mask::matches(&entry.mask, hostmask, casemapping))
+    }
+
+    /// Tests whether a user is banned and not exempt from the ban.
+    pub fn is_banned(&self, hostmask: &str, casemapping: Casemapping) -> bool {
+        self.list_matches('b', hostmask, casemapping) && !self.list_matches('e', hostmask, casemapping)
+    }
+
+    /// Tests whether a user may join without an invite.
+    pub fn is_invite_exempt(&self, hostmask: &str, casemapping: Casemapping) -> bool {
+        self.list_matches('I', hostmask, casemapping)
+    }
+
+    /// Tests whether the channel is hidden from users outside the channel.
+    pub fn is_hidden(&self) -> bool {
+        self.secret || self.private
+    }
+
+    /// Gets the NAMES symbol of the channel.
+    /// 
+    /// - `@` - Secret channel (+s)
+    /// - `*` - Private channel (+p)
+    /// - `=` - Public channel
+    pub fn names_symbol(&self) -> &'static str {
+        if self.secret {
+            "@"
+        } else if self.private {
+            "*"
+        } else {
+            "="
+        }
+    }
+
+    /// Builds the mode string and mode arguments of the channel.
+    /// 
+    /// - `show_key` - Whether to reveal the channel key
+    pub fn to_mode_string(&self, show_key: bool) -> (String, Vec<String>) {
+        let mut modes = String::from("+");
+        let mut arguments = Vec::new();
+        let flags = [
+            ('i', self.invite_only),
+            ('m', self.moderated),
+            ('n', self.no_external_messages),
+            ('p', self.private),
+            ('s', self.secret),
+            ('t', self.protected_topic),
+        ];
+        for (mode, _) in flags.iter().filter(|(_, set)| *set) {
+            modes.push(*mode);
+        }
+        if let Some(key) = &self.key {
+            modes.push('k');
+            arguments.push(if show_key { key.clone() } else { "*".to_owned() });
+        }
+        if let Some(limit) = self.limit {
+            modes.push('l');
+            arguments.push(limit.to_string());
+        }
+        (modes, arguments)
+    }
+}
\ No newline at end of file
@@ -0,0 +1,68 @@
+/// The tokens advertised to clients through RPL_ISUPPORT.
+pub struct ISupport {
+    tokens: Vec<(String, Option<String>)>,
+}
+
+impl ISupport {
+
+    /// The maximum number of tokens sent in a single RPL_ISUPPORT line
+    const MAX_TOKENS_PER_LINE: usize = 13;
+
+    pub fn new() -> Self {
+        Self {
+            tokens: Vec::new(),
+        }
+    }
+
+    /// Adds a token, replacing any previous value of the same token.
+    /// 
+    /// - `token` - The name of the token, e.g. `PREFIX`
+    /// - `value` - The value of the token, if any
+    pub fn add(&mut self, token: &str, value: Option<String>) {
+        let entry = (token.to_owned(), value);
+        match self.tokens.iter().position(|(name, _)| name == token) {
+            Some(i) => self.tokens[i] = entry,
+            None => self.tokens.push(entry),
+        }
+    }
+
+    /// Escapes a token value, e.g. `Zircon Net` becomes `Zircon\x20Net`.
+    fn escape(value: &str) -> String {
+        value.chars()
+            .map(|chr| match chr {
+                '\\' => "\\x5C".to_owned(),
+                ' ' => "\\x20".to_owned(),
+                '=' => "\\x3D".to_owned(),
+                chr => chr.to_string(),
+            })
+            .collect()
+    }
+
+    /// Splits the formatted tokens into RPL_ISUPPORT lines.
+    /// 
+    /// - `max_len` - The space available for the tokens of a single line
+    pub fn lines(&self, max_len: usize) -> Vec<Vec<String>> {
+        let mut lines: Vec<Vec<String>> = Vec::new();
+        let mut line: Vec<String> = Vec::new();
+        let mut line_len = 0;
+        for (name, value) in &self.tokens {
+            let token = match value {
+                Some(value) => format!("{}={}", name, ISupport::escape(value)),
+                None => name.clone(),
+            };
+
+            // Start a new line if the token doesn't fit into the current one
+            if !line.is_empty() && (line.len() >= ISupport::MAX_TOKENS_PER_LINE || line_len + token.len() + 1 > max_len) {
+                lines.push(line);
+                line = Vec::new();
+                line_len = 0;
+            }
+            line_len += token.len() + 1;
+            line.push(token);
+        }
+        if !line.is_empty() {
+            lines.push(line);
+        }
+        lines
+    }
+}
\ No newline at end of file
@@ -4,31 +4,142 @@ use std::net::TcpListener;
 use std::thread;
 use std::sync::Arc;
 use std::sync::RwLock;
-use std::sync::mpsc::channel;
+use std::sync::mpsc::{channel, RecvTimeoutError};
+use std::time::{Duration, Instant};
 
 use crate::config::ServerConfig;
+use crate::casemapping::Casemapping;
 use crate::message::{IrcMessageRequest, IrcMessageCommand, Respond};
-use super::{User, UserList, ChannelList, IrcAction};
+use crate::parser::ParseError;
+use super::{User, UserList, ChannelList, ChannelModes, WhowasList, ISupport, IrcAction};
 
 pub struct Server {
     pub config: ServerConfig,
+    pub config_path: String,
     pub users: UserList,
     pub channels: ChannelList,
+    pub whowas: WhowasList,
+    pub isupport: ISupport,
+    pub motd: Option<String>,
+    pub casemapping: Casemapping,
+    pub start_time: u64,
+    pub max_users: usize,
     pub last_state: Option<String>,
 }
 
 impl Server {
-    pub fn new(config: ServerConfig) -> Self {
+    pub fn new(config: ServerConfig, config_path: String) -> Self {
+        let casemapping = config.get_casemapping();
+        let isupport = Server::build_isupport(&config, casemapping);
+        let motd = Server::read_motd(&config);
         let mut server = Self {
             config,
-            users: UserList::new(),
-            channels: ChannelList::new(),
+            config_path,
+            users: UserList::new(casemapping),
+            channels: ChannelList::new(casemapping),
+            whowas: WhowasList::new(casemapping),
+            isupport,
+            motd,
+            casemapping,
+            start_time: crate::time::timestamp(),
+            max_users: 0,
             last_state: None,
         };
         server.restore_state();
         server
     }
 
+    /// Replaces the server configuration, e.g. after a rehash.
+    /// 
+    /// The casemapping can't be changed while the server is running.
+    pub fn set_config(&mut self, config: ServerConfig) {
+        self.isupport = Server::build_isupport(&config, self.casemapping);
+        self.motd = Server::read_motd(&config);
+        self.config = config;
+    }
+
+    /// Sends a PING to registered users that have been idle for too long
+    /// and disconnects users that didn't reply in time.
+    fn check_idle_connections(&mut self) {
+        let ping_interval = self.config.get_ping_interval();
+
+        // Collect the idle users first, as dispatching needs mutable access to the server
+        let idle_users: Vec<_> = self.users.iter_mut()
+            .filter(|user| user.is_registered() && user.time_since_activity() >= ping_interval)
+            .filter_map(|user| user.stream().try_clone().ok().map(|stream| (stream, user.id)))
+            .collect();
+
+        for (mut client, client_id) in idle_users {
+            crate::dispatch::dispatch(&crate::dispatch::Keepalive {}, self, &mut client, client_id);
+        }
+    }
+
+    /// Hides the parameters of commands carrying passwords, so they never end up in the log.
+    fn redact_line(line: &str) -> String {
+        const REDACTED_COMMANDS: &[&str] = &["PASS", "OPER"];
+
+        // Skip the tags and the prefix to find the command
+        let mut length = 0;
+        for word in line.split(' ') {
+            length += word.len() + 1;
+            if word.is_empty() || word.starts_with('@') || word.starts_with(':') {
+                continue;
+            }
+            if REDACTED_COMMANDS.contains(&word.trim_end().to_ascii_uppercase().as_ref()) && length < line.len() {
+                return format!("{} <redacted>\n", &line[..length - 1]);
+            }
+            break;
+        }
+        line.to_owned()
+    }
+
+    /// Reads the message of the day from the configured file, if it exists.
+    fn read_motd(config: &ServerConfig) -> Option<String> {
+        let mut file = File::open(config.get_motd_file()).ok()?;
+        let mut motd = String::new();
+        file.read_to_string(&mut motd).ok()?;
+        Some(motd)
+    }
+
+    /// Builds the ISUPPORT tokens from the server capabilities.
+    fn build_isupport(config: &ServerConfig, casemapping: Casemapping) -> ISupport {
+        let mut isupport = ISupport::new();
+
+        // Network
+        isupport.add("NETWORK", Some(config.get_network().to_owned()));
+        isupport.add("CASEMAPPING", Some(casemapping.name().to_owned()));
+        isupport.add("NICKLEN", Some(config.get_nick_length().to_string()));
+
+        // Channels
+        isupport.add("CHANTYPES", Some("#".to_owned()));
+        isupport.add("CHANNELLEN", Some(config.get_channel_length().to_string()));
+        isupport.add("TOPICLEN", Some(config.get_topic_length().to_string()));
+
+        // Channel modes, grouped into list modes, modes that always take a parameter,
+        // modes that only take a parameter when set and modes without a parameter
+        isupport.add("CHANMODES", Some(format!("{},k,l,{}", ChannelModes::LIST_MODES, ChannelModes::SIMPLE_MODES)));
+        isupport.add("MODES", Some(config.get_max_modes().to_string()));
+
+        // Channel member status modes
+        let status_modes = config.get_status_modes();
+        let modes: String = status_modes.iter().map(|(mode, _)| mode).collect();
+        let prefixes: String = status_modes.iter().map(|(_, prefix)| prefix).collect();
+        isupport.add("PREFIX", Some(format!("({}){}", modes, prefixes)));
+
+        // Ban, exception and invite exception lists
+        isupport.add("EXCEPTS", Some("e".to_owned()));
+        isupport.add("INVEX", Some("I".to_owned()));
+        isupport.add("MAXLIST", Some(format!("beI:{}", config.get_max_list_entries())));
+
+        // Channel list filters
+        isupport.add("ELIST", Some(crate::dispatch::List::ELIST.to_owned()));
+
+        // Maximum number of targets per command
+        isupport.add("TARGMAX", Some(format!("JOIN:,PART:,KICK:{},NAMES:,LIST:,WHOIS:,PRIVMSG:1,NOTICE:1", crate::dispatch::Kick::MAX_TARGETS)));
+
+        isupport
+    }
+
     pub fn save_state(&mut self) -> std::io::Result<()> {
 
         // Serialize state to string
@@ -37,7 +148,7 @@ impl Server {
         
         // Determine whether the state needs to be updated
         let update_state = match &self.last_state {
-            Some(last_state) => last_state == &state,
+            Some(last_state) => last_state != &state,
             _ => true,
         };
 
@@ -64,9 +175,10 @@ impl Server {
         file.read_to_string(&mut buf).expect("Unable to read the state file.");
 
         // Deserialize the state
-        let state: ChannelList = toml::from_str(&buf).expect("Unable to parse the state file.");
+        let mut state: ChannelList = toml::from_str(&buf).expect("Unable to parse the state file.");
 
         // Update the current state
+        state.set_casemapping(self.casemapping);
         self.channels = state;
     }
 
@@ -77,6 +189,7 @@ impl Server {
 
         // Create asynchronous channel
         let (sender, recv) = channel();
+        let timeout_sender = sender.clone();
 
         // Bind the tcp listener socket
         let listener = TcpListener::bind(self.config.get_addr()).unwrap();
@@ -121,23 +234,38 @@ impl Server {
 
                         // Read the next line
                         let mut line = String::new();
-                        reader.read_line(&mut line).unwrap();
+                        let read_result = reader.read_line(&mut line);
 
-                        // Test for EOF
-                        if line.is_empty() {
-                            sender.send((client, client_id, IrcAction::Disconnect())).unwrap();
+                        // Test for EOF or a broken connection
+                        if read_result.is_err() || line.is_empty() {
+                            sender.send((client, client_id, IrcAction::Disconnect(None))).unwrap();
                             break;
                         }
 
                         // Debug
-                        print!("[{:?}] {}", addr, line);
+                        print!("[{:?}] {}", addr, Server::redact_line(&line));
 
                         // Parse the irc message
-                        let cmd = IrcMessageRequest::parse(&line);
+                        let cmd = match IrcMessageRequest::parse(&line) {
+                            Ok(cmd) => cmd,
+
+                            // Empty lines are silently ignored
+                            Err(ParseError::EmptyMessage) => continue,
+
+                            // Tell the client about invalid messages
+                            Err(error) => {
+                                sender.send((client, client_id, IrcAction::InvalidMessage(error))).unwrap();
+                                continue;
+                            }
+                        };
 
                         // Handle the command
                         match cmd.command {
 
+                            IrcMessageCommand::Pass(password) => {
+                                sender.send((client, client_id, IrcAction::UserSetPassword(password))).unwrap()
+                            }
+
                             IrcMessageCommand::Nick(nickname) => {
                                 sender.send((client, client_id, IrcAction::UserSetNick(nickname))).unwrap()
                             }
@@ -146,6 +274,10 @@ impl Server {
                                 sender.send((client, client_id, IrcAction::UserSetNames(username, realname))).unwrap()
                             }
 
+                            IrcMessageCommand::Cap(subcommand, capabilities) => {
+                                sender.send((client, client_id, IrcAction::UserCapability(subcommand, capabilities))).unwrap()
+                            }
+
                             IrcMessageCommand::Join(channels, keys) => {
 
                                 // Minor optimization if there is only one channel.
@@ -180,24 +312,128 @@ impl Server {
                                 }
                             }
 
+                            IrcMessageCommand::Topic(channel, topic) => {
+                                sender.send((client, client_id, IrcAction::ChannelTopic(channel, topic))).unwrap()
+                            }
+
                             IrcMessageCommand::Privmsg(target, message) => {
                                 sender.send((client, client_id, IrcAction::Privmsg(target, message))).unwrap()
                             }
 
-                            IrcMessageCommand::Who(channel) => {
-                                sender.send((client, client_id, IrcAction::ChannelListUsers(channel))).unwrap();
+                            IrcMessageCommand::Notice(target, message) => {
+                                sender.send((client, client_id, IrcAction::Notice(target, message))).unwrap()
+                            }
+
+                            IrcMessageCommand::Invite(Some((nickname, channel))) => {
+                                sender.send((client, client_id, IrcAction::ChannelInvite(nickname, channel))).unwrap();
+                            }
+
+                            IrcMessageCommand::Invite(None) => {
+                                sender.send((client, client_id, IrcAction::ChannelListInvites())).unwrap();
+                            }
+
+                            IrcMessageCommand::Kick(channel, nicknames, reason) => {
+                                sender.send((client, client_id, IrcAction::ChannelKick(channel, nicknames, reason))).unwrap();
+                            }
+
+                            IrcMessageCommand::Mode(target, modes, arguments) if target.starts_with('#') => {
+                                sender.send((client, client_id, IrcAction::ChannelMode(target, modes, arguments))).unwrap();
+                            }
+
+                            IrcMessageCommand::Mode(nickname, modes, _) => {
+                                sender.send((client, client_id, IrcAction::UserMode(nickname, modes))).unwrap();
+                            }
+
+                            IrcMessageCommand::Names(channels) => {
+                                sender.send((client, client_id, IrcAction::ChannelListUsers(channels))).unwrap();
+                            }
+
+                            IrcMessageCommand::List(filters) => {
+                                sender.send((client, client_id, IrcAction::ChannelListChannels(filters))).unwrap();
+                            }
+
+                            IrcMessageCommand::Who(mask, options) => {
+                                sender.send((client, client_id, IrcAction::ChannelWho(mask, options))).unwrap();
+                            }
+
+                            IrcMessageCommand::Whois(nicknames) => {
+                                sender.send((client, client_id, IrcAction::UserWhois(nicknames))).unwrap();
+                            }
+
+                            IrcMessageCommand::Userhost(nicknames) => {
+                                sender.send((client, client_id, IrcAction::UserUserhost(nicknames))).unwrap();
+                            }
+
+                            IrcMessageCommand::Ison(nicknames) => {
+                                sender.send((client, client_id, IrcAction::UserIson(nicknames))).unwrap();
+                            }
+
+                            IrcMessageCommand::Whowas(nickname, count) => {
+                                sender.send((client, client_id, IrcAction::UserWhowas(nickname, count))).unwrap();
+                            }
+
+                            IrcMessageCommand::Away(message) => {
+                                sender.send((client, client_id, IrcAction::UserAway(message))).unwrap();
+                            }
+
+                            IrcMessageCommand::Oper(name, password) => {
+                                sender.send((client, client_id, IrcAction::UserOper(name, password))).unwrap();
+                            }
+
+                            IrcMessageCommand::Kill(nickname, reason) => {
+                                sender.send((client, client_id, IrcAction::UserKill(nickname, reason))).unwrap();
+                            }
+
+                            IrcMessageCommand::Version => {
+                                sender.send((client, client_id, IrcAction::ServerVersion())).unwrap();
+                            }
+
+                            IrcMessageCommand::Motd => {
+                                sender.send((client, client_id, IrcAction::ServerMotd())).unwrap();
+                            }
+
+                            IrcMessageCommand::Lusers => {
+                                sender.send((client, client_id, IrcAction::ServerLusers())).unwrap();
+                            }
+
+                            IrcMessageCommand::Time => {
+                                sender.send((client, client_id, IrcAction::ServerTime())).unwrap();
+                            }
+
+                            IrcMessageCommand::Info => {
+                                sender.send((client, client_id, IrcAction::ServerInfo())).unwrap();
+                            }
+
+                            IrcMessageCommand::Admin => {
+                                sender.send((client, client_id, IrcAction::ServerAdmin())).unwrap();
+                            }
+
+                            IrcMessageCommand::Rehash => {
+                                sender.send((client, client_id, IrcAction::ServerRehash())).unwrap();
+                            }
+
+                            IrcMessageCommand::Die => {
+                                sender.send((client, client_id, IrcAction::ServerDie())).unwrap();
                             }
 
                             IrcMessageCommand::Ping(id) => {
                                 sender.send((client, client_id, IrcAction::Pong(id))).unwrap();
                             }
 
+                            IrcMessageCommand::Pong => {
+                                sender.send((client, client_id, IrcAction::UserPong())).unwrap();
+                            }
+
+                            IrcMessageCommand::Quit(message) => {
+
+                                // The server closes the connection once the quit has been handled
+                                sender.send((client, client_id, IrcAction::Disconnect(message))).unwrap();
+                                break;
+                            }
+
                             com => println!("Unhandled command: {:?}", com),
                         }
                     }
-
-                    // Kill the client
-                    shared_client.shutdown(std::net::Shutdown::Both).unwrap();
                 });
 
                 // Keep track of the thread handle
@@ -207,13 +443,30 @@ impl Server {
 
         // Macro for simple server-to-client communication
         macro_rules! send {
-            ($writer:expr; $variant:expr) => (
-                $writer.write_all(format!("{}\r\n", $variant.to_string()).as_ref()).unwrap()
-            );
+            ($writer:expr; $variant:expr) => {{
+                $writer.write_all(format!("{}\r\n", $variant.to_string()).as_ref()).ok();
+            }};
         }
 
+        // Idle connections are checked once per second
+        let keepalive_interval = Duration::from_secs(1);
+        let mut last_keepalive = Instant::now();
+
         // Receive actions
-        for (mut client, client_id, action) in recv {
+        loop {
+
+            // Check the connections of idle users
+            if last_keepalive.elapsed() >= keepalive_interval {
+                last_keepalive = Instant::now();
+                self.check_idle_connections();
+            }
+
+            // Wait for the next action, but wake up in time for the next check
+            let (mut client, client_id, action) = match recv.recv_timeout(keepalive_interval) {
+                Ok(message) => message,
+                Err(RecvTimeoutError::Timeout) => continue,
+                Err(RecvTimeoutError::Disconnected) => break,
+            };
 
             macro_rules! dispatch {
                 ($dispatcher:expr) => {
@@ -231,15 +484,68 @@ impl Server {
                 }
             }
 
+            // Drop the remaining actions of users that have already been disconnected,
+            // e.g. lines that were read before the server closed the connection
+            if !matches!(action, IrcAction::UserConnect()) && self.users.find(client_id).is_none() {
+                continue;
+            }
+
+            // Any message from the client proves that the connection is alive
+            if action.is_client_message() {
+                if let Some(user) = self.users.find_mut(client_id) {
+                    user.mark_active();
+                }
+            }
+
+            // Only registration commands are accepted before the registration has been completed
+            if action.requires_registration() && !self.users.find(client_id).is_some_and(|user| user.is_registered()) {
+                send!(client; Respond::to(self.config.get_host(), "*").err_not_registered());
+                continue;
+            }
+
             // Handle the action
             #[allow(unreachable_patterns)]
             match action {
 
                 IrcAction::UserConnect() => {
+
+                    // Close the connection if the registration takes too long
+                    let sender = timeout_sender.clone();
+                    let timeout = self.config.get_registration_timeout();
+                    let timeout_client = client.try_clone().unwrap();
+                    thread::spawn(move || {
+                        thread::sleep(Duration::from_secs(timeout));
+                        sender.send((timeout_client, client_id, IrcAction::UserRegistrationTimeout())).ok();
+                    });
+
                     let user = User::new(client_id, client);
                     self.users.add(user);
                 }
+
+                IrcAction::UserRegistrationTimeout() => {
+
+                    // Registered users and closed connections are not affected
+                    if self.users.find(client_id).is_some_and(|user| !user.is_registered()) {
+                        dispatch!(crate::dispatch::Quit {
+                            message: Some("Registration timed out".to_owned()),
+                        })
+                    }
+                }
                 
+                IrcAction::UserSetPassword(password) => {
+
+                    // The password can't be changed after the registration
+                    if my_user!(r).is_registered() {
+                        send!(client; Respond::to(self.config.get_host(), &my_user!(r).nickname()).err_already_registered());
+                    } else if let Some(password) = password {
+
+                        // Remember the password until the registration is completed
+                        my_user!(rw).set_password(password);
+                    } else {
+                        send!(client; Respond::to(self.config.get_host(), "*").err_need_more_params("PASS"));
+                    }
+                }
+
                 IrcAction::UserSetNick(nickname) => {
                     dispatch!(crate::dispatch::SetNick {
                         nickname,
@@ -248,8 +554,24 @@ impl Server {
 
                 IrcAction::UserSetNames(username, realname) => {
 
-                    // Set username and realname
-                    my_user!(rw).set_names(username, realname);
+                    // The names can't be changed after the registration
+                    if my_user!(r).is_registered() {
+                        send!(client; Respond::to(self.config.get_host(), &my_user!(r).nickname()).err_already_registered());
+                    } else {
+
+                        // Set username and realname
+                        my_user!(rw).set_names(username, realname);
+
+                        // Complete the registration if NICK has been received
+                        dispatch!(crate::dispatch::Register {})
+                    }
+                }
+
+                IrcAction::UserCapability(subcommand, capabilities) => {
+                    dispatch!(crate::dispatch::Capability {
+                        subcommand,
+                        capabilities,
+                    })
                 }
 
                 IrcAction::UserJoinChannel(channel_name, channel_key) => {
@@ -266,41 +588,156 @@ impl Server {
                     })
                 }
 
-                IrcAction::ChannelListUsers(_channel_name) => {
+                IrcAction::UserMode(nickname, modes) => {
+                    dispatch!(crate::dispatch::UserMode {
+                        nickname,
+                        modes,
+                    })
+                }
+
+                IrcAction::UserWhois(nicknames) => {
+                    dispatch!(crate::dispatch::Whois {
+                        nicknames,
+                    })
+                }
+
+                IrcAction::UserUserhost(nicknames) => {
+                    dispatch!(crate::dispatch::Userhost {
+                        nicknames,
+                    })
+                }
+
+                IrcAction::UserIson(nicknames) => {
+                    dispatch!(crate::dispatch::Ison {
+                        nicknames,
+                    })
+                }
+
+                IrcAction::UserWhowas(nickname, count) => {
+                    dispatch!(crate::dispatch::Whowas {
+                        nickname,
+                        count,
+                    })
+                }
+
+                IrcAction::UserAway(message) => {
+                    dispatch!(crate::dispatch::Away {
+                        message,
+                    })
+                }
+
+                IrcAction::UserOper(name, password) => {
+                    dispatch!(crate::dispatch::Oper {
+                        name,
+                        password,
+                    })
+                }
+
+                IrcAction::UserKill(nickname, reason) => {
+                    dispatch!(crate::dispatch::Kill {
+                        nickname,
+                        reason,
+                    })
+                }
+
+                IrcAction::ServerVersion() => {
+                    dispatch!(crate::dispatch::Version {})
+                }
+
+                IrcAction::ServerMotd() => {
+                    dispatch!(crate::dispatch::Motd {})
+                }
+
+                IrcAction::ServerLusers() => {
+                    dispatch!(crate::dispatch::Lusers {})
+                }
+
+                IrcAction::ServerTime() => {
+                    dispatch!(crate::dispatch::Time {})
+                }
+
+                IrcAction::ServerInfo() => {
+                    dispatch!(crate::dispatch::Info {})
+                }
+
+                IrcAction::ServerAdmin() => {
+                    dispatch!(crate::dispatch::Admin {})
+                }
+
+                IrcAction::ServerRehash() => {
+                    dispatch!(crate::dispatch::Rehash {})
+                }
+
+                IrcAction::ServerDie() => {
+                    dispatch!(crate::dispatch::Die {})
+                }
+
+                IrcAction::ChannelTopic(channel_name, topic) => {
+                    dispatch!(crate::dispatch::Topic {
+                        channel_name,
+                        topic,
+                    })
+                }
+
+                IrcAction::ChannelInvite(nickname, channel_name) => {
+                    dispatch!(crate::dispatch::Invite {
+                        nickname,
+                        channel_name,
+                    })
+                }
 
-                    // // Find the user
-                    // if let Some(user) = self.users.find(client_id) {
+                IrcAction::ChannelListInvites() => {
+                    dispatch!(crate::dispatch::ListInvites {})
+                }
 
-                    //     // Find the channel
-                    //     if let Some(channel) = self.channels.find(&channel_name) {
+                IrcAction::ChannelKick(channel_name, nicknames, reason) => {
+                    dispatch!(crate::dispatch::Kick {
+                        channel_name,
+                        nicknames,
+                        reason,
+                    })
+                }
 
-                    //         // Iterate over all users in the channel
-                    //         for user_info in channel.users() {
-                                
-                    //             // Find the user
-                    //             if let Some(channel_user) = self.users.find(user_info.client_id()) {
+                IrcAction::ChannelMode(channel_name, modes, arguments) => {
+                    dispatch!(crate::dispatch::ChannelMode {
+                        channel_name,
+                        modes,
+                        arguments,
+                    })
+                }
 
-                    //                 // Get channel mode
-                    //                 // "=": public
-                    //                 // "@": secret (+s)
-                    //                 // "*": private (+p)
-                    //                 let channel_mode = "=";
+                IrcAction::ChannelListUsers(channel_names) => {
+                    dispatch!(crate::dispatch::Names {
+                        channel_names,
+                    })
+                }
 
-                    //                 // Tell the client about the user
-                    //                 send!(client; Respond::to(self.config.get_host(), &user.nickname()).names_reply(&channel_name, channel_mode, &channel_user.nickname()))
-                    //             }
-                    //         }
+                IrcAction::ChannelListChannels(filters) => {
+                    dispatch!(crate::dispatch::List {
+                        filters,
+                    })
+                }
 
-                    //         // Mark the end of the user list
-                    //         send!(client; Respond::to(self.config.get_host(), &user.nickname()).names_end(&channel_name));
-                    //     }
-                    // }
+                IrcAction::ChannelWho(mask, options) => {
+                    dispatch!(crate::dispatch::Who {
+                        mask,
+                        options,
+                    })
                 }
 
                 IrcAction::Privmsg(target, message) => {
                     dispatch!(crate::dispatch::PrivateMessage {
                         target,
                         message,
+                        notice: false,
+                    })
+                }
+
+                IrcAction::Notice(target, message) => {
+                    dispatch!(crate::dispatch::PrivateMessage {
+                        target,
+                        message,
+                        notice: true,
                     })
                 }
 
@@ -310,11 +747,24 @@ impl Server {
                     send!(client; Respond::to(self.config.get_host(), &my_user!(r).nickname()).pong(id));
                 }
 
-                IrcAction::Disconnect() => {
+                IrcAction::UserPong() => {
+
+                    // The activity of the user has already been recorded
+                }
+
+                IrcAction::InvalidMessage(error) => {
+                    dispatch!(crate::dispatch::InvalidMessage {
+                        error,
+                    })
+                }
+
+                IrcAction::Disconnect(message) => {
 
                     // Disconnect the user
                     println!("Connection lost: {}", my_user!(r).nickname());
-                    self.users.disconnect(client_id);
+                    dispatch!(crate::dispatch::Quit {
+                        message,
+                    })
                 }
 
                 _ => println!("Unimplemented action: {:?}", action)
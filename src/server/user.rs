@@ -1,21 +1,78 @@
 use std::net::TcpStream;
 
+use crate::casemapping::Casemapping;
+use crate::config::OperPrivilege;
+use crate::name::Nickname;
+use super::UserModes;
+
+/// The registration progress of a connection.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Registration {
+
+    /// The client is still sending NICK and USER, or negotiating capabilities
+    Pending {
+        nick: bool,
+        user: bool,
+        negotiating: bool,
+    },
+
+    /// The welcome burst has been sent
+    Registered,
+}
+
 #[derive(Debug)]
 pub struct User {
     pub id: usize,
     nickname: Option<String>,
     username: Option<String>,
     realname: Option<String>,
+    hostname: String,
+    signon_time: u64,
+    idle_since: u64,
+    last_activity: u64,
+    awaiting_pong: bool,
+    nick_changed_at: u64,
+    invites: Vec<String>,
+    modes: UserModes,
+    privileges: Vec<OperPrivilege>,
+    away: Option<String>,
+    account: Option<String>,
+    capabilities: Vec<String>,
+    registration: Registration,
+    password: Option<String>,
+    failed_opers: u32,
     stream: TcpStream,
 }
 
 impl User {
     pub fn new(id: usize, stream: TcpStream) -> User {
+
+        // Use the remote address as the hostname
+        let hostname = match stream.peer_addr() {
+            Ok(addr) => addr.ip().to_string(),
+            _ => "unknown".to_owned(),
+        };
+
         Self {
             id,
             nickname: None,
             username: None,
             realname: None,
+            hostname,
+            signon_time: crate::time::timestamp(),
+            idle_since: crate::time::timestamp(),
+            last_activity: crate::time::timestamp(),
+            awaiting_pong: false,
+            nick_changed_at: 0,
+            invites: Vec::new(),
+            modes: UserModes::default(),
+            privileges: Vec::new(),
+            away: None,
+            account: None,
+            capabilities: Vec::new(),
+            registration: Registration::Pending { nick: false, user: false, negotiating: false },
+            password: None,
+            failed_opers: 0,
             stream,
         }
     }
@@ -49,12 +106,189 @@ impl User {
         }
     }
 
-    pub fn set_nickname(&mut self, nickname: String) {
-        self.nickname = Some(nickname);
+    pub fn hostname(&self) -> &str {
+        self.hostname.as_ref()
+    }
+
+    /// Returns the full `nick!user@host` mask of the user.
+    pub fn hostmask(&self) -> String {
+        format!("{}!{}@{}", self.nickname(), self.username.as_ref().map_or("*", |s| s.as_ref()), self.hostname)
+    }
+
+    pub fn signon_time(&self) -> u64 {
+        self.signon_time
+    }
+
+    /// Returns the number of seconds since the user last sent a message.
+    pub fn idle_time(&self) -> u64 {
+        crate::time::timestamp().saturating_sub(self.idle_since)
+    }
+
+    /// Resets the idle time of the user.
+    pub fn reset_idle_time(&mut self) {
+        self.idle_since = crate::time::timestamp();
+    }
+
+    /// Gets the number of seconds since the client sent the last message.
+    pub fn time_since_activity(&self) -> u64 {
+        crate::time::timestamp().saturating_sub(self.last_activity)
+    }
+
+    /// Remembers that the client sent a message.
+    /// 
+    /// Any message proves that the connection is still alive,
+    /// so a pending PING doesn't need to be answered anymore.
+    pub fn mark_active(&mut self) {
+        self.last_activity = crate::time::timestamp();
+        self.awaiting_pong = false;
+    }
+
+    pub fn is_awaiting_pong(&self) -> bool {
+        self.awaiting_pong
+    }
+
+    /// Remembers that the client was sent a PING.
+    pub fn set_awaiting_pong(&mut self) {
+        self.awaiting_pong = true;
+    }
+
+    pub fn modes(&self) -> &UserModes {
+        &self.modes
+    }
+
+    pub fn modes_mut(&mut self) -> &mut UserModes {
+        &mut self.modes
+    }
+
+    /// Grants server operator status along with the privileges of an operator class.
+    pub fn set_operator(&mut self, privileges: Vec<OperPrivilege>) {
+        self.modes.operator = true;
+        self.privileges = privileges;
+    }
+
+    /// Gets the name of the account the user is logged in to, if any.
+    /// Nothing logs users in yet, so this is always `None` for now.
+    pub fn account(&self) -> Option<&str> {
+        self.account.as_ref().map(|account| account.as_ref())
+    }
+
+    /// Counts a failed OPER attempt and returns the number of failed attempts so far.
+    pub fn add_failed_oper(&mut self) -> u32 {
+        self.failed_opers += 1;
+        self.failed_opers
+    }
+
+    /// Tests whether the user is a server operator with the specified privilege.
+    pub fn has_privilege(&self, privilege: OperPrivilege) -> bool {
+        self.modes.operator && self.privileges.contains(&privilege)
+    }
+
+    /// Gets the away message of the user, if the user is away.
+    pub fn away(&self) -> Option<&str> {
+        self.away.as_ref().map(|away| away.as_ref())
+    }
+
+    pub fn set_away(&mut self, message: Option<String>) {
+        self.away = message;
+    }
+
+    /// Gets the IRCv3 capabilities enabled by the client.
+    pub fn capabilities(&self) -> &Vec<String> {
+        &self.capabilities
+    }
+
+    pub fn has_capability(&self, capability: &str) -> bool {
+        self.capabilities.iter().any(|enabled| enabled == capability)
+    }
+
+    /// Enables or disables an IRCv3 capability.
+    pub fn set_capability(&mut self, capability: &str, enabled: bool) {
+        self.capabilities.retain(|other| other != capability);
+        if enabled {
+            self.capabilities.push(capability.to_owned());
+        }
+    }
+
+    /// Gets the channels the user has been invited to.
+    pub fn invites(&self) -> &Vec<String> {
+        &self.invites
+    }
+
+    /// Remembers an invite to a channel.
+    pub fn add_invite(&mut self, channel_name: &str, casemapping: Casemapping) {
+        if !self.has_invite(channel_name, casemapping) {
+            self.invites.push(channel_name.to_owned());
+        }
+    }
+
+    pub fn has_invite(&self, channel_name: &str, casemapping: Casemapping) -> bool {
+        self.invites.iter().any(|invite| casemapping.equals(invite, channel_name))
+    }
+
+    /// Removes an invite to a channel once it has been used.
+    pub fn remove_invite(&mut self, channel_name: &str, casemapping: Casemapping) {
+        self.invites.retain(|invite| !casemapping.equals(invite, channel_name));
+    }
+
+    /// Returns the number of seconds since the user last changed the nickname after the registration.
+    pub fn time_since_nick_change(&self) -> u64 {
+        crate::time::timestamp().saturating_sub(self.nick_changed_at)
+    }
+
+    pub fn set_nickname(&mut self, nickname: Nickname) {
+        self.nickname = Some(nickname.into());
+        if self.is_registered() {
+            self.nick_changed_at = crate::time::timestamp();
+        }
+        if let Registration::Pending { ref mut nick, .. } = self.registration {
+            *nick = true;
+        }
     }
 
     pub fn set_names(&mut self, username: String, realname: Option<String>) {
         self.realname = Some(realname.unwrap_or_else(|| username.clone()));
         self.username = Some(username);
+        if let Registration::Pending { ref mut user, .. } = self.registration {
+            *user = true;
+        }
+    }
+
+    pub fn is_registered(&self) -> bool {
+        self.registration == Registration::Registered
+    }
+
+    /// Suspends or resumes the registration during capability negotiation.
+    pub fn set_negotiating(&mut self, value: bool) {
+        if let Registration::Pending { ref mut negotiating, .. } = self.registration {
+            *negotiating = value;
+        }
+    }
+
+    /// Remembers the password sent with PASS until the registration is completed.
+    pub fn set_password(&mut self, password: String) {
+        self.password = Some(password);
+    }
+
+    pub fn password(&self) -> Option<&str> {
+        self.password.as_ref().map(|password| password.as_ref())
+    }
+
+    /// Tests whether both NICK and USER have been received
+    /// and capability negotiation has ended.
+    pub fn can_register(&self) -> bool {
+        self.registration == Registration::Pending { nick: true, user: true, negotiating: false }
+    }
+
+    /// Completes the registration if possible.
+    /// 
+    /// Returns `true` if the user has just been registered.
+    pub fn complete_registration(&mut self) -> bool {
+        if self.can_register() {
+            self.registration = Registration::Registered;
+            self.password = None;
+            true
+        } else {
+            false
+        }
     }
 }
\ No newline at end of file
@@ -1,13 +1,16 @@
 use super::User;
+use crate::casemapping::Casemapping;
 
 pub struct UserList {
     users: Vec<User>,
+    casemapping: Casemapping,
 }
 
 impl UserList {
-    pub fn new() -> Self {
+    pub fn new(casemapping: Casemapping) -> Self {
         Self {
             users: Vec::new(),
+            casemapping,
         }
     }
 
@@ -15,6 +18,19 @@ impl UserList {
         self.users.push(user);
     }
 
+    pub fn iter(&self) -> impl Iterator<Item = &User> {
+        self.users.iter()
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut User> {
+        self.users.iter_mut()
+    }
+
+    /// Counts the users that completed their registration.
+    pub fn registered_count(&self) -> usize {
+        self.users.iter().filter(|user| user.is_registered()).count()
+    }
+
     pub fn find(&self, client_id: usize) -> Option<&User> {
         self.users.iter().find(|user| user.id == client_id)
     }
@@ -23,12 +39,16 @@ impl UserList {
         self.users.iter_mut().find(|user| user.id == client_id)
     }
 
+    /// Finds a user by nickname, ignoring case.
     pub fn find_by_name(&self, nick: &str) -> Option<&User> {
-        self.users.iter().find(|user| user.nickname() == nick)
+        let casemapping = self.casemapping;
+        self.users.iter().find(|user| user.has_nickname() && casemapping.equals(&user.nickname(), nick))
     }
 
+    /// Finds a user by nickname, ignoring case.
     pub fn find_by_name_mut(&mut self, nick: &str) -> Option<&mut User> {
-        self.users.iter_mut().find(|user| user.nickname() == nick)
+        let casemapping = self.casemapping;
+        self.users.iter_mut().find(|user| user.has_nickname() && casemapping.equals(&user.nickname(), nick))
     }
 
     pub fn disconnect(&mut self, client_id: usize) -> bool {
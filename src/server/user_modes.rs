@@ -0,0 +1,68 @@
+/// The modes of a user.
+#[derive(Debug, Default)]
+pub struct UserModes {
+
+    /// `+i` - The user is hidden from users outside shared channels
+    pub invisible: bool,
+
+    /// `+w` - The user receives wallops
+    pub wallops: bool,
+
+    /// `+D` - The user receives no channel messages
+    pub deaf: bool,
+
+    /// `+o` - The user is a server operator
+    pub operator: bool,
+
+    /// `+s` - The user receives server notices
+    pub server_notices: bool,
+}
+
+impl UserModes {
+
+    /// All supported user modes.
+    pub const SUPPORTED_MODES: &'static str = "Diosw";
+
+    /// Modes that can only be set by the server.
+    pub const SERVER_MODES: &'static str = "o";
+
+    fn mode_mut(&mut self, mode: char) -> Option<&mut bool> {
+        match mode {
+            'i' => Some(&mut self.invisible),
+            'w' => Some(&mut self.wallops),
+            'D' => Some(&mut self.deaf),
+            'o' => Some(&mut self.operator),
+            's' => Some(&mut self.server_notices),
+            _ => None,
+        }
+    }
+
+    /// Sets or unsets a user mode.
+    /// 
+    /// Returns `true` if the mode has changed.
+    pub fn set_mode(&mut self, mode: char, value: bool) -> bool {
+        match self.mode_mut(mode) {
+            Some(current) if *current != value => {
+                *current = value;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Builds the mode string of the user, e.g. `+iw`.
+    pub fn to_mode_string(&self) -> String {
+        let mut modes = String::from("+");
+        let flags = [
+            ('D', self.deaf),
+            ('i', self.invisible),
+            ('o', self.operator),
+            ('s', self.server_notices),
+            ('w', self.wallops),
+        ];
+        for (mode, _) in flags.iter().filter(|(_, set)| *set) {
+            modes.push(*mode);
+        }
+        modes
+    }
+}
\ No newline at end of file
@@ -0,0 +1,59 @@
+use std::collections::VecDeque;
+
+use super::User;
+use crate::casemapping::Casemapping;
+
+pub struct WhowasEntry {
+    pub nickname: String,
+    pub username: String,
+    pub hostname: String,
+    pub realname: String,
+    pub time: u64,
+}
+
+impl WhowasEntry {
+    pub fn new(user: &User) -> Self {
+        Self {
+            nickname: user.nickname(),
+            username: user.username(),
+            hostname: user.hostname().to_owned(),
+            realname: user.realname(),
+            time: crate::time::timestamp(),
+        }
+    }
+}
+
+/// A bounded history of nicknames that are no longer in use.
+pub struct WhowasList {
+    entries: VecDeque<WhowasEntry>,
+    casemapping: Casemapping,
+}
+
+impl WhowasList {
+
+    /// The maximum number of entries kept in the history
+    const MAX_ENTRIES: usize = 256;
+
+    pub fn new(casemapping: Casemapping) -> Self {
+        Self {
+            entries: VecDeque::new(),
+            casemapping,
+        }
+    }
+
+    /// Records the current identity of a user.
+    /// 
+    /// The oldest entry is dropped once the history is full.
+    pub fn add(&mut self, user: &User) {
+        if self.entries.len() >= Self::MAX_ENTRIES {
+            self.entries.pop_back();
+        }
+        self.entries.push_front(WhowasEntry::new(user));
+    }
+
+    /// Finds all entries for a nickname, ignoring case, most recent first.
+    pub fn find_by_name(&self, nick: &str) -> impl Iterator<Item = &WhowasEntry> {
+        let (nick, casemapping) = (nick.to_owned(), self.casemapping);
+        self.entries.iter().filter(move |entry| casemapping.equals(&entry.nickname, &nick))
+    }
+}
\ No newline at end of file
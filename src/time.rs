@@ -0,0 +1,45 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Returns the current time as seconds since the unix epoch.
+pub fn timestamp() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or(0)
+}
+
+/// Formats seconds since the unix epoch as a human readable UTC date,
+/// e.g. `Sunday October 18 2026 -- 12:34:56 +00:00`.
+pub fn format_timestamp(timestamp: u64) -> String {
+    const WEEKDAYS: [&str; 7] = ["Thursday", "Friday", "Saturday", "Sunday", "Monday", "Tuesday", "Wednesday"];
+    const MONTHS: [&str; 12] = [
+        "January", "February", "March", "April", "May", "June",
+        "July", "August", "September", "October", "November", "December",
+    ];
+
+    let days = timestamp / 86400;
+    let seconds = timestamp % 86400;
+
+    // Convert the days since the epoch to a civil date.
+    // The calculation is based on eras of 400 years starting on March 1st.
+    let days_since_era = days + 719_468;
+    let era = days_since_era / 146_097;
+    let day_of_era = days_since_era % 146_097;
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = if shifted_month < 10 { shifted_month + 3 } else { shifted_month - 9 };
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+
+    format!(
+        "{} {} {} {} -- {:02}:{:02}:{:02} +00:00",
+        WEEKDAYS[(days % 7) as usize],
+        MONTHS[(month - 1) as usize],
+        day,
+        year,
+        seconds / 3600,
+        seconds / 60 % 60,
+        seconds % 60,
+    )
+}
\ No newline at end of file